@@ -0,0 +1,29 @@
+use tease::{Audio, Input, Output, Teaser};
+
+fn main() {
+    Teaser::<f32>::default()
+        .with_title("Backwards".to_string())
+        .with_description("Record something, and hear it played in reverse.".to_string())
+        .with_inputs(vec![Input::Audio {
+            label: Some("Recording".to_string()),
+            sample_rate: Some(16_000),
+        }])
+        .with_outputs(vec![
+            Output::Audio {
+                label: Some("Reversed".to_string()),
+            },
+            Output::Number {
+                label: Some("Seconds".to_string()),
+                precision: 2,
+            },
+        ])
+        .with_typed_function(|(recording,): (Audio,)| {
+            let duration = recording.duration();
+            let reversed = Audio {
+                samples: recording.samples.into_iter().rev().collect(),
+                sample_rate: recording.sample_rate,
+            };
+            (reversed, duration)
+        })
+        .run();
+}
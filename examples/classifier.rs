@@ -0,0 +1,58 @@
+use smartcore::{
+    dataset::iris,
+    linalg::{naive::dense_matrix::DenseMatrix, BaseMatrix},
+    linear::logistic_regression::LogisticRegression,
+};
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    let iris = iris::load_dataset();
+    let x = DenseMatrix::from_array(iris.num_samples, iris.num_features, &iris.data);
+    let model = LogisticRegression::fit(&x, &iris.target, Default::default()).unwrap();
+
+    let measurement = |label: &str, initial_value: f32| Input::Slider {
+        label: Some(label.to_string()),
+        min: 0.0,
+        max: 8.0,
+        step: 0.1,
+        initial_value,
+    };
+
+    Teaser::default()
+        .with_title("Iris Classifier".to_string())
+        .with_description("This predicts the species of an iris from its measurements.".to_string())
+        .with_inputs(vec![
+            measurement("Sepal Length (cm)", 5.1),
+            measurement("Sepal Width (cm)", 3.5),
+            measurement("Petal Length (cm)", 1.4),
+            measurement("Petal Width (cm)", 0.2),
+        ])
+        .with_output(Output::Label {
+            label: Some("Species".to_string()),
+            class_names: vec![
+                "Setosa".to_string(),
+                "Versicolor".to_string(),
+                "Virginica".to_string(),
+            ],
+            top_k: 3,
+        })
+        .with_typed_function(move |(x0, x1, x2, x3): (f32, f32, f32, f32)| {
+            // The probability of each class is the softmax of the model's score for it
+            let (coefficients, intercept) = (model.coefficients(), model.intercept());
+            let scores: Vec<f32> = (0..coefficients.shape().0)
+                .map(|class| {
+                    [x0, x1, x2, x3]
+                        .iter()
+                        .enumerate()
+                        .map(|(feature, x)| coefficients.get(class, feature) * x)
+                        .sum::<f32>()
+                        + intercept.get(class, 0)
+                })
+                .collect();
+            let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            let exp: Vec<f32> = scores.iter().map(|score| (score - max).exp()).collect();
+            let total: f32 = exp.iter().sum();
+            (exp.iter().map(|e| e / total).collect::<Vec<_>>(),)
+        })
+        .run();
+}
@@ -0,0 +1,24 @@
+use tease::{Input, Output, Teaser, Upload};
+
+fn main() {
+    Teaser::<f32>::default()
+        .with_title("Word Count".to_string())
+        .with_description("Count the words in a text file.".to_string())
+        .with_inputs(vec![Input::File {
+            label: Some("Document".to_string()),
+            extensions: vec![".txt".to_string(), ".md".to_string()],
+            max_size: Some(1_000_000),
+        }])
+        .with_output(Output::Text {
+            label: Some("Words".to_string()),
+        })
+        .with_typed_function(|(document,): (Upload,)| {
+            let text = String::from_utf8_lossy(&document.bytes);
+            format!(
+                "{} has {} words",
+                document.name,
+                text.split_whitespace().count()
+            )
+        })
+        .run();
+}
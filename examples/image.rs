@@ -0,0 +1,22 @@
+use tease::{ColorMode, Input, Output, PixelLayout, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Brightness".to_string())
+        .with_description(
+            "Find out how bright an image is, from 0 (black) to 1 (white).".to_string(),
+        )
+        .with_inputs(vec![Input::Image {
+            label: Some("Photo".to_string()),
+            width: 64,
+            height: 64,
+            color_mode: ColorMode::Grayscale,
+            layout: PixelLayout::Hwc,
+        }])
+        .with_output(Output::Number {
+            label: Some("Brightness".to_string()),
+            precision: 3,
+        })
+        .with_function(|pixels: Vec<f32>| pixels.iter().sum::<f32>() / pixels.len() as f32)
+        .run();
+}
@@ -0,0 +1,26 @@
+use tease::{ColorMode, Image, Input, Output, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Color Mixer".to_string())
+        .with_description("Mix red, green and blue into a swatch.".to_string())
+        .with_inputs(
+            ["Red", "Green", "Blue"]
+                .iter()
+                .map(|color| Input::Slider {
+                    label: Some(color.to_string()),
+                    min: 0.0,
+                    max: 1.0,
+                    step: 0.01,
+                    initial_value: 0.5,
+                })
+                .collect(),
+        )
+        .with_output(Output::Image {
+            label: Some("Swatch".to_string()),
+        })
+        .with_typed_function(|(red, green, blue): (f32, f32, f32)| {
+            Image::from_floats(64, 64, ColorMode::Rgb, &[red, green, blue].repeat(64 * 64))
+        })
+        .run();
+}
@@ -0,0 +1,28 @@
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Determinant".to_string())
+        .with_description("Find the determinant of a 3x3 matrix.".to_string())
+        .with_inputs(vec![Input::Matrix {
+            label: Some("Matrix".to_string()),
+            rows: 3,
+            cols: 3,
+            initial_values: vec![
+                vec![2.0, 0.0, 1.0],
+                vec![1.0, 3.0, 2.0],
+                vec![1.0, 1.0, 1.0],
+            ],
+            editable_dims: false,
+        }])
+        .with_output(Output::Number {
+            label: Some("Determinant".to_string()),
+            precision: 3,
+        })
+        .with_typed_function(|(m,): (Vec<Vec<f64>>,)| {
+            m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+        })
+        .run();
+}
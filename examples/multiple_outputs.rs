@@ -0,0 +1,25 @@
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Statistics".to_string())
+        .with_description("This summarizes a handful of measurements.".to_string())
+        .with_inputs(vec![Input::default(); 4])
+        .with_outputs(vec![
+            Output::Number {
+                label: Some("Mean".to_string()),
+                precision: 2,
+            },
+            Output::Number {
+                label: Some("Standard Deviation".to_string()),
+                precision: 3,
+            },
+        ])
+        .with_multi_function(|x: Vec<f64>| {
+            let n = x.len() as f64;
+            let mean = x.iter().sum::<f64>() / n;
+            let variance = x.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / n;
+            (mean, variance.sqrt())
+        })
+        .run();
+}
@@ -0,0 +1,38 @@
+use tease::{Input, Output, PlotKind, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Damped Oscillator".to_string())
+        .with_description("See how a spring settles down over time.".to_string())
+        .with_inputs(vec![
+            Input::Slider {
+                label: Some("Damping".to_string()),
+                min: 0.0,
+                max: 1.0,
+                step: 0.05,
+                initial_value: 0.2,
+            },
+            Input::Slider {
+                label: Some("Frequency".to_string()),
+                min: 0.5,
+                max: 5.0,
+                step: 0.5,
+                initial_value: 2.0,
+            },
+        ])
+        .with_output(Output::Plot {
+            label: Some("Position".to_string()),
+            kind: PlotKind::Line,
+            x_label: Some("Time (s)".to_string()),
+            y_label: Some("Position (m)".to_string()),
+        })
+        .with_typed_function(|(damping, frequency): (f64, f64)| {
+            (0..=200)
+                .map(|step| {
+                    let t = step as f64 * 0.05;
+                    (t, (-damping * t).exp() * (frequency * t).cos())
+                })
+                .collect::<Vec<_>>()
+        })
+        .run();
+}
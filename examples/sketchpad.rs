@@ -0,0 +1,19 @@
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Ink".to_string())
+        .with_description("Draw something to see how much of the pad it covers.".to_string())
+        .with_inputs(vec![Input::Sketchpad {
+            label: Some("Drawing".to_string()),
+            width: 28,
+            height: 28,
+            brush_size: 2,
+        }])
+        .with_output(Output::Number {
+            label: Some("Coverage".to_string()),
+            precision: 3,
+        })
+        .with_function(|grid: Vec<f32>| grid.iter().sum::<f32>() / grid.len() as f32)
+        .run();
+}
@@ -41,8 +41,11 @@ fn main() {
             },
             Input::Dropdown {
                 label: Some("Year".to_string()),
-                options: (1940..2022).step_by(1).map(f64::from).collect(),
+                options: (1940..2022)
+                    .map(|year| (year.to_string(), f64::from(year)))
+                    .collect(),
                 initial_value: 0,
+                one_hot: false,
             },
         ])
         .with_function(move |x| {
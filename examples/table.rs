@@ -0,0 +1,45 @@
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    let planets = [
+        ("Mercury", 0.39, 0.055),
+        ("Venus", 0.72, 0.815),
+        ("Earth", 1.0, 1.0),
+        ("Mars", 1.52, 0.107),
+        ("Jupiter", 5.2, 317.8),
+        ("Saturn", 9.54, 95.2),
+        ("Uranus", 19.2, 14.5),
+        ("Neptune", 30.06, 17.1),
+    ];
+    Teaser::default()
+        .with_title("Nearest Planets".to_string())
+        .with_description("Find the planets closest to a distance from the sun.".to_string())
+        .with_inputs(vec![
+            Input::Number {
+                label: Some("Distance (AU)".to_string()),
+                initial_value: 1.2,
+            },
+            Input::Slider {
+                label: Some("Neighbours".to_string()),
+                min: 1.0,
+                max: 8.0,
+                step: 1.0,
+                initial_value: 3.0,
+            },
+        ])
+        .with_output(Output::Table {
+            label: Some("Planets".to_string()),
+            columns: vec![
+                "Planet".to_string(),
+                "Distance (AU)".to_string(),
+                "Mass (Earths)".to_string(),
+            ],
+        })
+        .with_typed_function(move |(distance, neighbours): (f64, usize)| {
+            let mut nearest = planets.to_vec();
+            nearest.sort_by(|a, b| (a.1 - distance).abs().total_cmp(&(b.1 - distance).abs()));
+            nearest.truncate(neighbours);
+            nearest
+        })
+        .run();
+}
@@ -0,0 +1,34 @@
+use tease::{Input, Output, Teaser, Value};
+
+fn main() {
+    Teaser::<f32>::default()
+        .with_title("Word Counter".to_string())
+        .with_description("This counts the words in a piece of text.".to_string())
+        .with_inputs(vec![Input::Text {
+            label: Some("Text".to_string()),
+            initial_value: "".to_string(),
+            multiline: true,
+            placeholder: Some("Type something here...".to_string()),
+        }])
+        .with_outputs(vec![
+            Output::Number {
+                label: Some("Words".to_string()),
+                precision: 0,
+            },
+            Output::Text {
+                label: Some("Longest Word".to_string()),
+            },
+        ])
+        .with_value_function(|x: Vec<Value>| {
+            let text = x[0].as_text().unwrap_or_default();
+            let longest = text
+                .split_whitespace()
+                .max_by_key(|word| word.len())
+                .unwrap_or_default();
+            (
+                Value::Float(text.split_whitespace().count() as f64),
+                Value::Text(longest.to_string()),
+            )
+        })
+        .run();
+}
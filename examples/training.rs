@@ -0,0 +1,64 @@
+use std::{thread, time::Duration};
+
+use tease::{Emitter, Input, Output, PlotKind, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Gradient Descent".to_string())
+        .with_description("Watch the loss fall as a line is fit to some noisy points.".to_string())
+        .with_inputs(vec![
+            Input::Slider {
+                label: Some("Learning rate".to_string()),
+                min: 0.01,
+                max: 0.5,
+                step: 0.01,
+                initial_value: 0.1,
+            },
+            Input::Number {
+                label: Some("Epochs".to_string()),
+                initial_value: 50.0,
+            },
+        ])
+        .with_outputs(vec![
+            Output::Plot {
+                label: Some("Loss".to_string()),
+                kind: PlotKind::Line,
+                x_label: Some("Epoch".to_string()),
+                y_label: Some("Mean squared error".to_string()),
+            },
+            Output::Text {
+                label: Some("Fit".to_string()),
+            },
+        ])
+        .with_streaming_function(|(learning_rate, epochs): (f64, usize), emitter: &Emitter| {
+            let points: Vec<(f64, f64)> = (0..20)
+                .map(|i| {
+                    let x = i as f64 / 10.0;
+                    (x, 3.0 * x + 1.0 + 0.2 * (i as f64 * 1.7).sin())
+                })
+                .collect();
+            let (mut slope, mut intercept) = (0.0, 0.0);
+            let mut losses = vec![];
+            for epoch in 0..epochs {
+                if emitter.is_cancelled() {
+                    break;
+                }
+                let (mut d_slope, mut d_intercept, mut loss) = (0.0, 0.0, 0.0);
+                for (x, y) in &points {
+                    let error = slope * x + intercept - y;
+                    d_slope += 2.0 * error * x / points.len() as f64;
+                    d_intercept += 2.0 * error / points.len() as f64;
+                    loss += error * error / points.len() as f64;
+                }
+                slope -= learning_rate * d_slope;
+                intercept -= learning_rate * d_intercept;
+                losses.push((epoch as f64, loss));
+
+                let fit = format!("y = {slope:.3}x + {intercept:.3}");
+                emitter.emit((losses.clone(), fit));
+                thread::sleep(Duration::from_millis(50));
+            }
+            (losses, format!("y = {slope:.3}x + {intercept:.3} (done)"))
+        })
+        .run();
+}
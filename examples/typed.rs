@@ -0,0 +1,36 @@
+use tease::{Input, Output, Teaser};
+
+fn main() {
+    Teaser::default()
+        .with_title("Greeter".to_string())
+        .with_inputs(vec![
+            Input::Text {
+                label: Some("Name".to_string()),
+                initial_value: "World".to_string(),
+                multiline: false,
+                placeholder: None,
+            },
+            Input::Slider {
+                label: Some("Excitement".to_string()),
+                min: 0.0,
+                max: 5.0,
+                step: 1.0,
+                initial_value: 1.0,
+            },
+        ])
+        .with_outputs(vec![
+            Output::Text {
+                label: Some("Greeting".to_string()),
+            },
+            Output::Number {
+                label: Some("Length".to_string()),
+                precision: 0,
+            },
+        ])
+        .with_typed_function(|(name, excitement): (String, usize)| {
+            let greeting = format!("Hello, {name}{}", "!".repeat(excitement));
+            let length = greeting.len();
+            (greeting, length)
+        })
+        .run();
+}
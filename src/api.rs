@@ -0,0 +1,316 @@
+//! Running the function for scripts, with the inputs and outputs keyed by their labels in plain
+//! JSON, along with an OpenAPI document that describes how
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use num_traits::Float;
+use serde_json::{json, Map, Value as Json};
+
+use crate::{Input, Output, Teaser};
+
+impl<F: Float + Display> Input<F> {
+    /// The JSON schema of the value of the input, as it is written in a request to the API
+    fn schema(&self) -> Json {
+        let number = |x: &F| x.to_f64().map_or(Json::Null, Json::from);
+        match self {
+            Input::Number { initial_value, .. } => {
+                json!({ "type": "number", "default": number(initial_value) })
+            }
+            Input::Text { initial_value, .. } => {
+                json!({ "type": "string", "default": initial_value })
+            }
+            Input::Slider {
+                min,
+                max,
+                initial_value,
+                ..
+            } => json!({
+                "type": "number",
+                "minimum": number(min),
+                "maximum": number(max),
+                "default": number(initial_value),
+            }),
+            Input::Dropdown {
+                options,
+                initial_value,
+                ..
+            }
+            | Input::Radio {
+                options,
+                initial_value,
+                ..
+            } => {
+                let texts: Vec<&String> = options.iter().map(|(text, _)| text).collect();
+                let mut schema = json!({ "type": "string", "enum": texts });
+                if let Some(text) = texts.get(*initial_value) {
+                    schema["default"] = json!(text);
+                }
+                schema
+            }
+            Input::Checkbox { initial_value, .. } => {
+                json!({ "type": "boolean", "default": initial_value })
+            }
+            Input::CheckboxGroup { options, .. } => json!({
+                "type": "array",
+                "items": { "type": "boolean" },
+                "minItems": options.len(),
+                "maxItems": options.len(),
+                "description": format!("Whether each option is checked: {}", options.join(", ")),
+            }),
+            Input::Image { width, height, .. } | Input::Sketchpad { width, height, .. } => json!({
+                "type": "string",
+                "format": "byte",
+                "description": format!(
+                    "The RGBA pixels of a {width}x{height} image, row by row, encoded as base64"
+                ),
+            }),
+            Input::File { .. } => json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "mime": { "type": "string" },
+                    "bytes": { "type": "string", "format": "byte" },
+                },
+                "required": ["name", "bytes"],
+            }),
+            Input::Audio { .. } => json!({
+                "type": "object",
+                "properties": {
+                    "sample_rate": { "type": "integer", "minimum": 1, "maximum": 768_000 },
+                    "samples": {
+                        "type": "string",
+                        "format": "byte",
+                        "description": "Little-endian 32-bit floats, encoded as base64",
+                    },
+                },
+                "required": ["sample_rate", "samples"],
+            }),
+            Input::Matrix {
+                rows,
+                cols,
+                editable_dims,
+                ..
+            } => {
+                let mut row = json!({ "type": "array", "items": { "type": "number" } });
+                let mut schema = json!({ "type": "array" });
+                if !editable_dims {
+                    row["minItems"] = json!(cols);
+                    row["maxItems"] = json!(cols);
+                    schema["minItems"] = json!(rows);
+                    schema["maxItems"] = json!(rows);
+                }
+                schema["items"] = row;
+                schema
+            }
+        }
+    }
+}
+
+impl Output {
+    /// The JSON schema of the value of the output, as it is written in a response from the API
+    fn schema(&self) -> Json {
+        let image = json!({
+            "type": "object",
+            "properties": {
+                "width": { "type": "integer" },
+                "height": { "type": "integer" },
+                "color_mode": { "type": "string", "enum": ["grayscale", "rgb", "rgba"] },
+                "pixels": { "type": "string", "format": "byte" },
+            },
+        });
+        match self {
+            Output::Number { .. } => json!({ "type": "number" }),
+            Output::Text { .. } => json!({ "type": "string" }),
+            Output::Label { .. } => json!({
+                "oneOf": [
+                    { "type": "number" },
+                    { "type": "array", "items": { "type": "number" } },
+                ],
+                "description": "The number of the class, or the probability of each class",
+            }),
+            Output::Audio { .. } => json!({
+                "type": "object",
+                "properties": {
+                    "sample_rate": { "type": "integer" },
+                    "samples": { "type": "string", "format": "byte" },
+                },
+            }),
+            Output::Plot { .. } => json!({ "type": "array" }),
+            Output::Table { .. } => json!({ "type": "array", "items": { "type": "array" } }),
+            Output::Image { .. } => json!({
+                "oneOf": [image, { "type": "string", "format": "byte" }],
+            }),
+        }
+    }
+}
+
+impl<F: 'static + Float + Display> Teaser<F> {
+    /// Check that no two inputs, and no two outputs, share a label, since the API keys them by it
+    pub(crate) fn check_labels(&self) -> Result<(), String> {
+        let inputs = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| input.get_label(idx));
+        if let Some(label) = duplicate(inputs) {
+            return Err(format!(
+                "Two inputs are labelled {label}, so the API can't tell them apart"
+            ));
+        }
+        // An advanced function picks its own outputs, so those are checked as they are returned
+        let outputs = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, output)| output.get_label(idx));
+        match duplicate(outputs) {
+            Some(label) if !self.use_advanced_function => Err(format!(
+                "Two outputs are labelled {label}, so the API can't tell them apart"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Answer a request to the API, whose body holds the value of each input keyed by its label,
+    /// with the status code and body of the response
+    pub(crate) fn predict(&self, body: &str) -> (u16, String) {
+        let error = |status, error: String| (status, json!({ "error": error }).to_string());
+        let values = match serde_json::from_str::<Map<String, Json>>(body) {
+            Ok(values) => values,
+            Err(err) => return error(400, format!("Malformed request: {err}")),
+        };
+
+        let named: BTreeMap<String, Json> = self
+            .inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, input)| {
+                let value = values.get(&input.get_label(idx))?;
+                Some((format!("x{idx}"), value.clone()))
+            })
+            .collect();
+        let inputs = match self.read_inputs(&named) {
+            Ok(inputs) => inputs,
+            Err(err) => return error(400, err),
+        };
+
+        match self.compute(inputs) {
+            Ok(outputs) => {
+                let labels = outputs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (output, _))| output.get_label(idx));
+                if let Some(label) = duplicate(labels) {
+                    return error(
+                        422,
+                        format!("The function returned two outputs labelled {label}"),
+                    );
+                }
+                let outputs: Map<String, Json> = outputs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (output, y))| (output.get_label(idx), y.to_json()))
+                    .collect();
+                (200, Json::Object(outputs).to_string())
+            }
+            Err(err) => error(422, err),
+        }
+    }
+
+    /// Describe the API as an OpenAPI 3 document
+    pub(crate) fn openapi(&self) -> Json {
+        let labels: Vec<String> = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| input.get_label(idx))
+            .collect();
+        let inputs: Map<String, Json> = labels
+            .iter()
+            .zip(&self.inputs)
+            .map(|(label, input)| (label.clone(), input.schema()))
+            .collect();
+
+        // An advanced function picks its own outputs, so they can't be known in advance
+        let outputs = if self.use_advanced_function {
+            json!({ "type": "object", "additionalProperties": true })
+        } else {
+            let outputs: Map<String, Json> = self
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(idx, output)| (output.get_label(idx), output.schema()))
+                .collect();
+            json!({ "type": "object", "properties": outputs })
+        };
+
+        let error = |description: &str| {
+            json!({
+                "description": description,
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Error" } },
+                },
+            })
+        };
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": self.title,
+                "description": self.description,
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": {
+                "/api/predict": {
+                    "post": {
+                        "summary": "Run the function on the value of each input, keyed by its label",
+                        "operationId": "predict",
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Inputs" },
+                                },
+                            },
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "The value of each output, keyed by its label",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Outputs" },
+                                    },
+                                },
+                            },
+                            "400": error("The inputs could not be read"),
+                            "422": error("The function could not be run on the inputs"),
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Inputs": {
+                        "type": "object",
+                        "properties": inputs,
+                        "required": labels,
+                    },
+                    "Outputs": outputs,
+                    "Error": {
+                        "type": "object",
+                        "properties": { "error": { "type": "string" } },
+                        "required": ["error"],
+                    },
+                },
+            },
+        })
+    }
+}
+
+/// The first label that appears more than once, if any
+fn duplicate(mut labels: impl Iterator<Item = String>) -> Option<String> {
+    let mut seen = BTreeSet::new();
+    labels.find(|label| !seen.insert(label.clone()))
+}
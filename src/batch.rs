@@ -0,0 +1,271 @@
+//! Running the function on every row of a CSV file, for the batch tab of the GUI
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use num_traits::Float;
+
+use crate::{
+    catch_panic, html_chunks::add_batch_output, protocol::Batch, Input, Output, Teaser, Value,
+};
+
+impl<F: Float + Display> Input<F> {
+    /// Whether the value of the input can be written in a cell of a CSV file
+    pub(crate) fn fits_in_csv(&self) -> bool {
+        !matches!(
+            self,
+            Input::Image { .. }
+                | Input::Sketchpad { .. }
+                | Input::File { .. }
+                | Input::Audio { .. }
+        )
+    }
+
+    /// The value of the input held by a cell of a CSV file, as it would have been sent by the page
+    fn read_cell(&self, cell: &str) -> serde_json::Value {
+        match self {
+            Input::Text { .. } | Input::Dropdown { .. } | Input::Radio { .. } => cell.into(),
+            _ => serde_json::from_str(cell).unwrap_or_else(|_| cell.trim().into()),
+        }
+    }
+}
+
+impl<F: 'static + Float + Display> Teaser<F> {
+    /// Run the function on each row of a CSV file, whose header names the inputs by their labels,
+    /// and hand back the file with a column added for each output
+    pub(crate) fn run_batch(&self, csv: &str) -> Result<Batch, String> {
+        Ok(self.read_batch(csv)?.run(|inputs| self.compute(inputs)))
+    }
+
+    /// Read the value of each input out of every row of a CSV file, whose header names the inputs
+    /// by their labels, so that the function can be run on them elsewhere
+    pub(crate) fn read_batch(&self, csv: &str) -> Result<BatchJob, String> {
+        let mut rows = parse_csv(csv).into_iter();
+        let header = rows.next().ok_or("The CSV file is empty")?;
+        let columns = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| {
+                let label = input.get_label(idx);
+                header
+                    .iter()
+                    .position(|name| name.trim() == label)
+                    .ok_or_else(|| format!("The CSV file has no column for {label}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rows = rows
+            .map(|row| {
+                let values: BTreeMap<_, _> = self
+                    .inputs
+                    .iter()
+                    .zip(&columns)
+                    .enumerate()
+                    .map(|(idx, (input, column))| {
+                        let cell = row.get(*column).map(String::as_str).unwrap_or_default();
+                        (format!("x{idx}"), input.read_cell(cell))
+                    })
+                    .collect();
+                let inputs = self.read_inputs(&values);
+                (row, inputs)
+            })
+            .collect();
+
+        // An advanced function picks its own outputs, so their labels come from its results
+        let output_labels = (!self.use_advanced_function).then(|| {
+            self.outputs
+                .iter()
+                .enumerate()
+                .map(|(idx, output)| output.get_label(idx))
+                .collect()
+        });
+        Ok(BatchJob {
+            header,
+            rows,
+            output_labels,
+        })
+    }
+}
+
+/// A row of a CSV file, along with the values of the inputs read out of it
+type Row = (Vec<String>, Result<Vec<Value>, String>);
+
+/// The rows of a CSV file, read and ready for the function to be run on them
+pub(crate) struct BatchJob {
+    /// The header of the file
+    header: Vec<String>,
+    /// Each row of the file
+    rows: Vec<Row>,
+    /// Labels of the outputs, or `None` if they are only known once the function has run
+    output_labels: Option<Vec<String>>,
+}
+
+impl BatchJob {
+    /// Run `compute` on each row, and hand back the file with a column added for each output
+    pub(crate) fn run(
+        self,
+        compute: impl Fn(Vec<Value>) -> Result<Vec<(Output, Value)>, String>,
+    ) -> Batch {
+        let results = self
+            .rows
+            .into_iter()
+            .map(|(row, inputs)| {
+                let result = inputs.and_then(&compute).and_then(|outputs| {
+                    catch_panic(|| {
+                        outputs
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, (output, y))| {
+                                Ok((output.get_label(idx), output.get_text(idx, y)?))
+                            })
+                            .collect::<Result<Vec<_>, String>>()
+                    })
+                });
+                (row, result)
+            })
+            .collect::<Vec<_>>();
+
+        let output_labels: Vec<String> = self.output_labels.unwrap_or_else(|| {
+            results
+                .iter()
+                .find_map(|(_, result)| result.as_ref().ok())
+                .map(|outputs| outputs.iter().map(|(label, _)| label.clone()).collect())
+                .unwrap_or_default()
+        });
+        let any_errors = results.iter().any(|(_, result)| result.is_err());
+
+        let mut header = self.header;
+        header.extend(output_labels.iter().cloned());
+        if any_errors {
+            header.push("Error".to_string());
+        }
+        let width = header.len();
+        let rows = results
+            .into_iter()
+            .map(|(mut row, result)| {
+                row.resize(
+                    width - output_labels.len() - usize::from(any_errors),
+                    String::new(),
+                );
+                match result {
+                    Ok(outputs) => {
+                        row.extend(outputs.into_iter().map(|(_, text)| text));
+                        row.resize(width, String::new());
+                    }
+                    Err(error) => {
+                        row.resize(width - 1, String::new());
+                        row.push(error);
+                    }
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let csv = write_csv(&header, &rows);
+        let display = add_batch_output(&header, &rows, &STANDARD.encode(&csv));
+        Batch { csv, display }
+    }
+}
+
+/// Split CSV text into rows of cells, unquoting quoted cells and skipping blank lines
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => cell.push(c),
+            (false, '"') if cell.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut cell)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') | (false, '\r') => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows.retain(|row| row.len() > 1 || row.first().is_some_and(|cell| !cell.is_empty()));
+    rows
+}
+
+/// Join rows of cells into CSV text, quoting any cell that needs it
+fn write_csv(header: &[String], rows: &[Vec<String>]) -> String {
+    let quote = |cell: &String| {
+        if cell.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+    std::iter::once(header)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|row| row.iter().map(quote).collect::<Vec<_>>().join(",") + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_csv, write_csv};
+
+    /// Build rows of owned cells out of string slices
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn quoted_commas_stay_in_their_cell() {
+        assert_eq!(parse_csv("a,\"b, c\",d\n"), rows(&[&["a", "b, c", "d"]]));
+    }
+
+    #[test]
+    fn doubled_quotes_are_unescaped() {
+        assert_eq!(
+            parse_csv("\"say \"\"hi\"\"\",x\n"),
+            rows(&[&["say \"hi\"", "x"]])
+        );
+    }
+
+    #[test]
+    fn crlf_and_lf_line_endings_match() {
+        let expected = rows(&[&["a", "b"], &["1", "2"]]);
+        assert_eq!(parse_csv("a,b\r\n1,2\r\n"), expected);
+        assert_eq!(parse_csv("a,b\n1,2\n"), expected);
+        assert_eq!(parse_csv("a,b\n1,2"), expected);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        assert_eq!(
+            parse_csv("a,b\n\n1,2\r\n\r\n\n3,4\n\n"),
+            rows(&[&["a", "b"], &["1", "2"], &["3", "4"]])
+        );
+    }
+
+    #[test]
+    fn written_files_parse_back() {
+        let header = vec!["plain".to_string(), "with, comma".to_string()];
+        let body = rows(&[
+            &["\"quoted\"", "line\nbreak"],
+            &["carriage\r\nreturn", ""],
+            &["", "last"],
+        ]);
+        let mut expected = vec![header.clone()];
+        expected.extend(body.clone());
+        assert_eq!(parse_csv(&write_csv(&header, &body)), expected);
+    }
+}
@@ -50,6 +50,40 @@
 #![doc = include_str!("../doc_chunks/dropdown.html")]
 //! "></iframe>
 //!
+//! Each option of a dropdown pairs the text shown to the user with the value handed to the
+//! function, so categories can be encoded however the model was trained. Setting `one_hot` hands
+//! the function a 1 or a 0 for each option instead.
+//! ```rust, no_run
+//! use tease::Input;
+//!
+//! let input = Input::Dropdown {
+//!     label: Some("Color".to_string()),
+//!     options: vec![
+//!         ("Red".to_string(), 0.0),
+//!         ("Green".to_string(), 1.0),
+//!         ("Blue".to_string(), 2.0),
+//!     ],
+//!     initial_value: 0,
+//!     one_hot: true,
+//! };
+//! # let _: Input<f32> = input;
+//! ```
+//!
+//! ### Radio Buttons
+//! Small sets of choices can be laid out side by side with an [`Input::Radio`](crate::Input::Radio)
+//! instead of being tucked away in a dropdown. Each option pairs the text shown beside its button
+//! with the value handed to the function.
+//! ```rust, no_run
+//! use tease::Input;
+//!
+//! let input = Input::Radio {
+//!     label: Some("Size".to_string()),
+//!     options: vec![("Small".to_string(), 1.0), ("Large".to_string(), 2.0)],
+//!     initial_value: 0,
+//! };
+//! # let _: Input<f32> = input;
+//! ```
+//!
 //! ## Fun with Closures
 //! By now, you've probably realized something - anything that you can fit in a closure can be used
 //! as the backend for a GUI. For instance, you can train a model in [SmartCore](https://smartcorelib.org/)
@@ -59,3 +93,159 @@
 //! ```
 //! ![](https://raw.githubusercontent.com/cmccomb/tease/master/assets/smartcore.png)
 //!
+//! Classifiers are just as easy. An [`Output::Label`](crate::Output::Label) shows the name of the
+//! class predicted by the model. Return a list of probabilities instead of a class number (from a
+//! function added using [`with_typed_function`](crate::Teaser::with_typed_function), say) and it
+//! will rank the most likely classes as well.
+//!```rust, no_run
+#![doc = include_str!("../examples/classifier.rs")]
+//! ```
+//!
+//! Models that take a small grid of numbers, like a window of sensor readings or a kernel, can
+//! use an [`Input::Matrix`](crate::Input::Matrix). A typed function gets it as a `Vec<Vec<f64>>`,
+//! one `Vec` for each row, and setting `editable_dims` lets the user add and remove rows and
+//! columns.
+//! ```rust, no_run
+#![doc = include_str!("../examples/matrix.rs")]
+//! ```
+//!
+//! ## More Than One Output
+//! A function can also report several things at once. Give the teaser one output for each of them
+//! with [`with_outputs`](crate::Teaser::with_outputs), and return them together (as a `Vec`, an
+//! array or a tuple) from a function added with
+//! [`with_multi_function`](crate::Teaser::with_multi_function). Each output keeps its own label and
+//! precision.
+//! ```rust, no_run
+#![doc = include_str!("../examples/multiple_outputs.rs")]
+//! ```
+//!
+//! ## Beyond Numbers
+//! Not every model works with numbers. Text can be typed into an
+//! [`Input::Text`](crate::Input::Text) and shown in an [`Output::Text`](crate::Output::Text), and a
+//! function added using [`with_value_function`](crate::Teaser::with_value_function) receives and
+//! returns [`Value`](crate::Value)s, which can hold either numbers or text.
+//! ```rust, no_run
+#![doc = include_str!("../examples/text.rs")]
+//! ```
+//!
+//! Unpacking [`Value`](crate::Value)s by hand gets old quickly, so a function added using
+//! [`with_typed_function`](crate::Teaser::with_typed_function) takes a tuple of ordinary rust types
+//! instead, one for each input, and can return a tuple with one value for each output. The types
+//! are checked against the inputs as soon as the GUI starts, so a mismatch is caught before anyone
+//! presses <kbd>Submit</kbd>.
+//! ```rust, no_run
+#![doc = include_str!("../examples/typed.rs")]
+//! ```
+//!
+//! Yes/no features fit in an [`Input::Checkbox`](crate::Input::Checkbox), and multi-hot features
+//! in an [`Input::CheckboxGroup`](crate::Input::CheckboxGroup). A function added using
+//! [`with_function`](crate::Teaser::with_function) sees each checkbox as a 1 or a 0 (a group
+//! contributes one number per option), while a typed function can take a `bool` or a `Vec<bool>`.
+//!
+//! Vision models can take an [`Input::Image`](crate::Input::Image). The image is resized in the
+//! GUI, and the function gets every pixel as a value between 0 and 1, in whichever
+//! [`ColorMode`](crate::ColorMode) and [`PixelLayout`](crate::PixelLayout) the model expects.
+//! ```rust, no_run
+#![doc = include_str!("../examples/image.rs")]
+//! ```
+//!
+//! For handwriting, an [`Input::Sketchpad`](crate::Input::Sketchpad) gives the user a canvas to draw
+//! on, and gives the function a grid of values that are 1 wherever the brush went.
+//! ```rust, no_run
+#![doc = include_str!("../examples/sketchpad.rs")]
+//! ```
+//!
+//! Anything else can come in through an [`Input::File`](crate::Input::File), which hands a typed
+//! function an [`Upload`](crate::Upload) with the name, type and contents of the file. The
+//! extensions and size of the files it takes can be limited, too.
+//! ```rust, no_run
+#![doc = include_str!("../examples/file.rs")]
+//! ```
+//!
+//! Speech and sound models can listen through an [`Input::Audio`](crate::Input::Audio), which
+//! takes a file or a recording from the microphone, and talk back through an
+//! [`Output::Audio`](crate::Output::Audio). Both work with an [`Audio`](crate::Audio), which holds
+//! the samples of a mono recording along with its sample rate.
+//! ```rust, no_run
+#![doc = include_str!("../examples/audio.rs")]
+//! ```
+//!
+//! Going the other way, an [`Output::Image`](crate::Output::Image) shows an [`Image`](crate::Image)
+//! returned by the function, which makes it easy to look at masks, saliency maps and generated
+//! pictures. A function that already has a PNG or JPEG file can return its bytes instead.
+//! ```rust, no_run
+#![doc = include_str!("../examples/image_output.rs")]
+//! ```
+//!
+//! Curves and time series belong in an [`Output::Plot`](crate::Output::Plot). Return a list of
+//! `(x, y)` points (or a list of such lists, for several series) and it is drawn as a line,
+//! scatter or bar chart, right in the GUI and without needing an internet connection.
+//! ```rust, no_run
+#![doc = include_str!("../examples/plot.rs")]
+//! ```
+//!
+//! When the answer is a list of things, like the nearest neighbours found by a model, an
+//! [`Output::Table`](crate::Output::Table) lays out the rows returned by the function (a `Vec` of
+//! tuples, say). The table can be sorted by clicking on a column, and copied to paste into a
+//! spreadsheet.
+//! ```rust, no_run
+#![doc = include_str!("../examples/table.rs")]
+//! ```
+//!
+//! ## Many Rows at Once
+//! Typing in one row at a time gets tedious when there is a whole spreadsheet to get through. As
+//! long as every input fits in a cell, the GUI has a _Batch_ tab that takes a CSV file with a column
+//! for each input, named after its label. The function is run on every row, and the results are
+//! shown in a table and can be downloaded as a copy of the file with a column added for each
+//! output. Rows that can't be run are kept, with the reason given in an extra _Error_ column.
+//!
+//! ## Live Updates
+//! Fast models are more fun to play with when the outputs keep up with the inputs. Turning on
+//! [`with_live`](crate::Teaser::with_live) reruns the function whenever an input changes (waiting
+//! for the user to stop typing first) and does away with the <kbd>Submit</kbd> button.
+//!
+//! ## Slow Models
+//! A function that takes a while would leave the window frozen until it is done. Hand it to
+//! [`with_background_function`](crate::Teaser::with_background_function) instead (it needs to be
+//! `Send` and `Sync`) and it is run on a worker thread, while the GUI shows how long it has been
+//! running and offers to cancel it.
+//!
+//! Iterative algorithms, like a training loop or a model that writes text a word at a time, can
+//! show their progress along the way. A function added using
+//! [`with_streaming_function`](crate::Teaser::with_streaming_function) also runs on a worker
+//! thread, and is handed an [`Emitter`](crate::Emitter) that updates the outputs whenever it is
+//! given new values. Whatever the function returns is shown once it is done. The emitter also
+//! knows when the run has been cancelled (or replaced by a newer one), so the function can stop
+//! early instead of finishing work that nobody will see.
+//! ```rust, no_run
+#![doc = include_str!("../examples/training.rs")]
+//! ```
+//!
+//! ## Sharing a Teaser
+//! A compiled binary only runs on the platform it was built for. A page written by
+//! [`export_html`](crate::Teaser::export_html) runs anywhere there is a browser, and can be sent
+//! around or put on a static web host as a single file. Compile the function to WebAssembly with
+//! [`export_wasm`](crate::export_wasm) and embed it using
+//! [`with_wasm_module`](crate::Teaser::with_wasm_module), and the page runs the model entirely in
+//! the browser.
+//!
+//! ## When Things Go Wrong
+//! Models don't always have an answer. If your function can fail, hand it to
+//! [`with_fallible_function`](crate::Teaser::with_fallible_function) instead and return a
+//! `Result`. Errors (along with inputs that can't be read and functions that panic) are shown in
+//! the GUI in place of the output, and the GUI stays open so the user can try again.
+//! ```rust, no_run
+//! use tease::{Input, Teaser};
+//!
+//! fn main() {
+//!     Teaser::default()
+//!         .with_title("Division".to_string())
+//!         .with_inputs(vec![Input::default(); 2])
+//!         .with_fallible_function(|x: Vec<f32>| match x[1] {
+//!             y if y == 0.0 => Err("Cannot divide by zero"),
+//!             y => Ok(x[0] / y),
+//!         })
+//!         .run();
+//! }
+//! ```
+//!
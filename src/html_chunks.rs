@@ -1,6 +1,9 @@
 use num_traits::Float;
 use std::fmt::Display;
 
+use crate::Value;
+
+/// The HTML for a number input, starting at `initial_value`
 pub(crate) fn add_number<F: Float + Display>(
     index: usize,
     initial_value: &F,
@@ -19,6 +22,37 @@ pub(crate) fn add_number<F: Float + Display>(
     ")
 }
 
+/// The HTML for a text input, or a text area when `multiline`
+pub(crate) fn add_text(
+    index: usize,
+    initial_value: &str,
+    multiline: bool,
+    placeholder: &Option<String>,
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let initial_value = escape(initial_value);
+    let placeholder = escape(placeholder.as_deref().unwrap_or_default());
+
+    if multiline {
+        format!("\
+        <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+        <textarea class=\"form-control input\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"text\" rows=\"4\" placeholder=\"{placeholder}\">{initial_value}</textarea>\
+        ")
+    } else {
+        format!("\
+        <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+        <input type=\"text\" class=\"form-control input\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"text\" placeholder=\"{placeholder}\" value=\"{initial_value}\">\
+        ")
+    }
+}
+
+/// The HTML for a slider between `min` and `max`, starting at `initial_value`
 pub(crate) fn add_slider<F: Float + Display>(
     index: usize,
     initial_value: &F,
@@ -42,10 +76,11 @@ pub(crate) fn add_slider<F: Float + Display>(
    </div>")
 }
 
+/// The HTML for a dropdown over the labels of `options`
 pub(crate) fn add_dropdown<F: Float + Display>(
     index: usize,
     initial_value_index: &usize,
-    options: &Vec<F>,
+    options: &[(String, F)],
     label: &Option<String>,
 ) -> String {
     let html_label = match label {
@@ -57,36 +92,810 @@ pub(crate) fn add_dropdown<F: Float + Display>(
     let mut output = format!(
         "\
     <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
-        <select class=\" form-control input\" aria-label=\"Default select example\">"
+        <select class=\" form-control input\" id=\"exampleInput{index}\" name=\"x{index}\" aria-label=\"Default select example\">"
     );
 
-    for (idx, option) in options.iter().enumerate() {
+    for (idx, (option, _)) in options.iter().enumerate() {
+        let option = escape(option);
         if idx == *initial_value_index {
             output = format!(
-                "{}<option selected value=\"{option}\">{option}</option>",
+                "{}<option selected value=\"{idx}\">{option}</option>",
                 output
             )
         } else {
-            output = format!("{}<option value=\"{option}\">{option}</option>", output)
+            output = format!("{}<option value=\"{idx}\">{option}</option>", output)
         }
     }
-    output = format!("{}, </select>", output);
+    output = format!("{}</select>", output);
     output
 }
 
-pub(crate) fn beginning(description: String) -> String {
+/// The HTML for a group of radio buttons over the labels of `options`
+pub(crate) fn add_radio<F>(
+    index: usize,
+    initial_value_index: usize,
+    options: &[(String, F)],
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let mut output = format!(
+        "\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input text-left\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"radio\">"
+    );
+
+    for (idx, (text, _)) in options.iter().enumerate() {
+        let text = escape(text);
+        let checked = if idx == initial_value_index {
+            "checked"
+        } else {
+            ""
+        };
+        output = format!(
+            "{output}<div class=\"form-check form-check-inline\">\
+            <input type=\"radio\" class=\"form-check-input\" id=\"exampleInput{index}_{idx}\" name=\"radio{index}\" value=\"{idx}\" {checked}>\
+            <label for=\"exampleInput{index}_{idx}\" class=\"form-check-label\">{text}</label>\
+            </div>"
+        );
+    }
+    format!("{output}</div>")
+}
+
+/// The channel through which the page hands its inputs back to rust
+#[derive(Clone)]
+pub(crate) enum Transport {
+    /// The `ipc` object injected into the page by wry
+    #[cfg(feature = "window")]
+    Ipc,
+    /// An HTTP `POST` back to the server that delivered the page
+    #[cfg(feature = "server")]
+    Http,
+    /// A WebAssembly module built with [`export_wasm`](crate::export_wasm), embedded in the page
+    /// as base64 (or left out, in which case the page can only show the inputs)
+    Wasm(Option<String>),
+}
+
+impl Transport {
+    /// The javascript that defines `send_message` for this transport
+    fn get_script(&self) -> String {
+        match self {
+            #[cfg(feature = "window")]
+            Transport::Ipc => "function send_message(message) {
+                    ipc.postMessage(message);
+                }"
+            .to_string(),
+            #[cfg(feature = "server")]
+            Transport::Http => "function send_message(message) {
+                    fetch('submit', {
+                        method: 'POST',
+                        headers: { 'Content-Type': 'application/json' },
+                        body: message,
+                    })
+                        .then(response => response.json())
+                        .catch(error => ({ id: JSON.parse(message).id, error: 'The server could not be reached: ' + error }))
+                        .then(handle_response);
+                }"
+            .to_string(),
+            Transport::Wasm(Some(module)) => format!(
+                "var teaser = WebAssembly.instantiate(Uint8Array.from(atob('{module}'), c => c.charCodeAt(0)))
+                    .then(result => result.instance.exports);
+                function send_message(message) {{
+                    teaser
+                        .then(exports => {{
+                            var bytes = new TextEncoder().encode(message);
+                            // Reserving room can grow the memory, which detaches any view of the old buffer
+                            var pointer = exports.tease_reserve(bytes.length);
+                            new Uint8Array(exports.memory.buffer, pointer, bytes.length).set(bytes);
+                            var length = exports.tease_respond(bytes.length);
+                            return JSON.parse(new TextDecoder().decode(new Uint8Array(exports.memory.buffer, exports.tease_buffer(), length)));
+                        }})
+                        .catch(error => ({{ id: JSON.parse(message).id, error: 'The model could not be run: ' + error }}))
+                        .then(handle_response);
+                }}"
+            ),
+            Transport::Wasm(None) => "function send_message(message) {
+                    handle_response({ id: JSON.parse(message).id, error: 'This page was exported without its model, so it can only show the inputs' });
+                }"
+            .to_string(),
+        }
+    }
+}
+
+/// The HTML for a single checkbox, ticked when `initial_value`
+pub(crate) fn add_checkbox(index: usize, initial_value: bool, label: &Option<String>) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let checked = if initial_value { "checked" } else { "" };
+
+    format!("\
+    <div class=\"form-check mt-3 text-left\">\
+    <input type=\"checkbox\" class=\"form-check-input input\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"checkbox\" {checked}>\
+    <label for=\"exampleInput{index}\" class=\"form-check-label\"><i>{html_label}</i></label>\
+    </div>")
+}
+
+/// The HTML for a group of checkboxes, with the ones in `initial_selected` ticked
+pub(crate) fn add_checkbox_group(
+    index: usize,
+    options: &[String],
+    initial_selected: &[usize],
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let mut output = format!(
+        "\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input text-left\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"checkbox-group\">"
+    );
+
+    for (idx, option) in options.iter().enumerate() {
+        let option = escape(option);
+        let checked = if initial_selected.contains(&idx) {
+            "checked"
+        } else {
+            ""
+        };
+        output = format!(
+            "{output}<div class=\"form-check form-check-inline\">\
+            <input type=\"checkbox\" class=\"form-check-input\" id=\"exampleInput{index}_{idx}\" {checked}>\
+            <label for=\"exampleInput{index}_{idx}\" class=\"form-check-label\">{option}</label>\
+            </div>"
+        );
+    }
+    format!("{output}</div>")
+}
+
+/// The HTML for an image upload, drawn onto a `width` by `height` canvas
+pub(crate) fn add_image(
+    index: usize,
+    width: usize,
+    height: usize,
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+
+    format!("\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input border rounded p-2\" name=\"x{index}\" data-kind=\"image\" ondragover=\"event.preventDefault()\" ondrop=\"event.preventDefault(); load_image(this, event.dataTransfer.files[0])\">\
+    <canvas class=\"border bg-white mb-2\" width=\"{width}\" height=\"{height}\" style=\"max-width: 100%\"></canvas>\
+    <input type=\"file\" class=\"form-control-file\" id=\"exampleInput{index}\" accept=\"image/*\" onchange=\"load_image(this.parentElement, this.files[0])\">\
+    <small class=\"form-text text-muted\">Choose an image, or drop one here</small>\
+    </div>")
+}
+
+/// The HTML for a `width` by `height` canvas to draw on with a brush of `brush_size`
+pub(crate) fn add_sketchpad(
+    index: usize,
+    width: usize,
+    height: usize,
+    brush_size: usize,
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+
+    format!("\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input\" name=\"x{index}\" data-kind=\"sketchpad\">\
+    <canvas class=\"border w-100\" id=\"exampleInput{index}\" width=\"{width}\" height=\"{height}\" data-brush=\"{brush_size}\" style=\"touch-action: none\" onpointerdown=\"sketch(event, this)\" onpointermove=\"sketch(event, this)\" onpointerup=\"sketch(event, this)\"></canvas>\
+    <button type=\"button\" class=\"btn btn-outline-secondary btn-sm mt-1\" onclick=\"clear_sketch(this.parentElement); input_changed()\">Clear</button>\
+    </div>")
+}
+
+/// The HTML for a file upload limited to `extensions`, and to `max_size` bytes if given
+pub(crate) fn add_file(
+    index: usize,
+    extensions: &[String],
+    max_size: Option<usize>,
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let accept = escape(&extensions.join(","));
+    let max_size = match max_size {
+        Some(max_size) => max_size.to_string(),
+        None => "".to_string(),
+    };
+
+    format!("\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <input type=\"file\" class=\"form-control-file input\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"file\" accept=\"{accept}\" data-max-size=\"{max_size}\" onchange=\"load_file(this)\">")
+}
+
+/// The HTML for an audio upload or recording, resampled to `sample_rate` if given
+pub(crate) fn add_audio(index: usize, sample_rate: Option<u32>, label: &Option<String>) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let sample_rate = match sample_rate {
+        Some(sample_rate) => sample_rate.to_string(),
+        None => "".to_string(),
+    };
+
+    format!("\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input border rounded p-2\" name=\"x{index}\" data-kind=\"audio\" data-sample-rate=\"{sample_rate}\">\
+    <audio class=\"w-100 mb-2\" controls></audio>\
+    <div class=\"d-flex align-items-center\">\
+    <input type=\"file\" class=\"form-control-file\" id=\"exampleInput{index}\" accept=\"audio/*\" onchange=\"load_audio(this.parentElement.parentElement, this.files[0])\">\
+    <button type=\"button\" class=\"btn btn-outline-danger btn-sm\" onclick=\"record_audio(this)\">Record</button>\
+    </div>\
+    </div>")
+}
+
+/// The HTML for a grid of `rows` by `cols` numbers, with buttons to resize it when `editable_dims`
+pub(crate) fn add_matrix<F: Float + Display>(
+    index: usize,
+    rows: usize,
+    cols: usize,
+    initial_values: &[Vec<F>],
+    editable_dims: bool,
+    label: &Option<String>,
+) -> String {
+    let html_label = match label {
+        None => {
+            format!("Input {index}")
+        }
+        Some(string) => string.to_string(),
+    };
+    let mut grid = String::new();
+    for row in 0..rows.max(1) {
+        grid = format!("{grid}<tr>");
+        for col in 0..cols.max(1) {
+            let value = match initial_values.get(row).and_then(|values| values.get(col)) {
+                Some(value) => value.to_string(),
+                None => "0".to_string(),
+            };
+            grid = format!(
+                "{grid}<td class=\"p-0\"><input type=\"text\" class=\"form-control form-control-sm border-0 text-center\" value=\"{value}\"></td>"
+            );
+        }
+        grid = format!("{grid}</tr>");
+    }
+    let buttons = if editable_dims {
+        "<div class=\"btn-group btn-group-sm\" role=\"group\">\
+        <button type=\"button\" class=\"btn btn-outline-secondary\" onclick=\"resize_matrix(this, 1, 0)\">+ Row</button>\
+        <button type=\"button\" class=\"btn btn-outline-secondary\" onclick=\"resize_matrix(this, -1, 0)\">&minus; Row</button>\
+        <button type=\"button\" class=\"btn btn-outline-secondary\" onclick=\"resize_matrix(this, 0, 1)\">+ Column</button>\
+        <button type=\"button\" class=\"btn btn-outline-secondary\" onclick=\"resize_matrix(this, 0, -1)\">&minus; Column</button>\
+        </div>"
+    } else {
+        ""
+    };
+
+    format!(
+        "\
+    <label for=\"exampleInput{index}\" class=\"col-form-label mt-3\"><i>{html_label}</i></label>\
+    <div class=\"input\" id=\"exampleInput{index}\" name=\"x{index}\" data-kind=\"matrix\">\
+    <table class=\"table table-sm table-bordered bg-white mb-1\"><tbody>{grid}</tbody></table>\
+    {buttons}\
+    </div>"
+    )
+}
+
+/// The HTML for a number output, with `value` already formatted
+pub(crate) fn add_number_output(index: usize, label: &str, value: &str) -> String {
+    let value = escape(value);
+    format!("<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <input type=\"text\" class=\"form-control\" id=\"output{index}\" name=\"output{index}\" aria-describedby=\"output{index}\" value=\"{value}\" readonly>")
+}
+
+/// The HTML for a text output showing `value`
+pub(crate) fn add_text_output(index: usize, label: &str, value: &str) -> String {
+    let value = escape(value);
+    format!("<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <textarea class=\"form-control\" id=\"output{index}\" name=\"output{index}\" aria-describedby=\"output{index}\" rows=\"3\" readonly>{value}</textarea>")
+}
+
+/// The HTML for a label output, showing the `predicted` class above the `ranked` probabilities
+pub(crate) fn add_label_output(
+    index: usize,
+    label: &str,
+    predicted: &str,
+    ranked: &[(String, f64)],
+) -> String {
+    let predicted = escape(predicted);
+    let mut output = format!(
+        "<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <div class=\"form-control h-auto\" id=\"output{index}\">
+             <h4 class=\"text-center my-2\">{predicted}&nbsp;</h4>"
+    );
+
+    for (name, probability) in ranked {
+        let name = escape(name);
+        let percent = 100.0 * probability.clamp(0.0, 1.0);
+        output = format!(
+            "{output}
+             <div class=\"d-flex justify-content-between small\"><span>{name}</span><span>{percent:.1}%</span></div>
+             <div class=\"progress mb-2\">
+                 <div class=\"progress-bar\" role=\"progressbar\" style=\"width: {percent}%\" aria-valuenow=\"{percent}\" aria-valuemin=\"0\" aria-valuemax=\"100\"></div>
+             </div>"
+        );
+    }
+    format!("{output}</div>")
+}
+
+/// The HTML for the results of a batch, a table of `rows` with a link to download them as a CSV
+pub(crate) fn add_batch_output(
+    header: &[String],
+    rows: &[Vec<String>],
+    encoded_csv: &str,
+) -> String {
+    let row = |cells: &[String], tag: &str| {
+        cells
+            .iter()
+            .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell)))
+            .collect::<String>()
+    };
+    let body = rows
+        .iter()
+        .map(|cells| format!("<tr>{}</tr>", row(cells, "td")))
+        .collect::<String>();
+
+    format!(
+        "<label class=\"col-form-label mt-3\"><i>Results for {} rows</i></label>
+         <div class=\"table-responsive\" style=\"max-height: 60vh\">
+             <table class=\"table table-sm table-striped bg-white\">
+                 <thead><tr>{}</tr></thead>
+                 <tbody>{body}</tbody>
+             </table>
+         </div>
+         <a class=\"btn btn-outline-primary\" download=\"results.csv\" href=\"data:text/csv;base64,{encoded_csv}\">Download CSV</a>",
+        rows.len(),
+        row(header, "th")
+    )
+}
+
+/// The HTML for an audio player, playing the base64 `wav` unless it is empty
+pub(crate) fn add_audio_output(index: usize, label: &str, wav: &str) -> String {
+    let src = if wav.is_empty() {
+        String::new()
+    } else {
+        format!("src=\"data:audio/wav;base64,{wav}\"")
+    };
+    format!(
+        "<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <audio class=\"w-100\" id=\"output{index}\" controls {src}></audio>"
+    )
+}
+
+/// The HTML for an image output, showing `src` unless it is empty
+pub(crate) fn add_image_output(index: usize, label: &str, src: &str) -> String {
+    let image = if src.is_empty() {
+        String::new()
+    } else {
+        format!("<img class=\"img-fluid border bg-white\" src=\"{src}\" alt=\"{label}\">")
+    };
+    format!(
+        "<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <div id=\"output{index}\">{image}</div>"
+    )
+}
+
+/// The HTML for a plot output, showing an already drawn `svg`
+pub(crate) fn add_plot_output(index: usize, label: &str, svg: &str) -> String {
+    format!(
+        "<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <div class=\"form-control h-auto bg-white\" id=\"output{index}\">{svg}</div>"
+    )
+}
+
+/// The HTML for a table output with a header of `columns`, filled with `rows`
+pub(crate) fn add_table_output(
+    index: usize,
+    label: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+) -> String {
+    let header = columns
+        .iter()
+        .map(|column| {
+            format!(
+                "<th role=\"button\" onclick=\"sort_table(this)\">{}</th>",
+                escape(column)
+            )
+        })
+        .collect::<String>();
+    let body = rows
+        .iter()
+        .map(|row| {
+            let cells = (0..columns.len())
+                .map(|column| match row.get(column) {
+                    None => "<td></td>".to_string(),
+                    Some(cell) => {
+                        let sort_key = match cell.as_float() {
+                            Some(x) => format!(" data-value=\"{x}\""),
+                            None => String::new(),
+                        };
+                        format!("<td{sort_key}>{}</td>", escape(&cell.to_string()))
+                    }
+                })
+                .collect::<String>();
+            format!("<tr>{cells}</tr>")
+        })
+        .collect::<String>();
+
+    format!(
+        "<label for=\"output{index}\" class=\"col-form-label mt-3\"><i>{label}</i></label>
+         <div id=\"output{index}\">
+             <div class=\"table-responsive\" style=\"max-height: 60vh\">
+                 <table class=\"table table-sm table-striped table-hover bg-white\">
+                     <thead><tr>{header}</tr></thead>
+                     <tbody>{body}</tbody>
+                 </table>
+             </div>
+             <button type=\"button\" class=\"btn btn-outline-secondary btn-sm\" onclick=\"copy_table(this)\">Copy</button>
+         </div>"
+    )
+}
+
+/// Escape text so that it shows up verbatim when placed in HTML
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// The start of the page, up to where the inputs go
+pub(crate) fn beginning(
+    title: &str,
+    description: String,
+    transport: Transport,
+    batch: bool,
+    live: bool,
+) -> String {
     let bootstrap = include_str!("bootstrap/bootstrap.min.css");
+    let send_message = transport.get_script();
+    let version = crate::protocol::VERSION;
+    let title = escape(title);
+    let tabs = if batch {
+        "<ul class=\"nav nav-tabs mt-3\">
+                            <li class=\"nav-item\"><a class=\"nav-link active\" data-toggle=\"tab\" href=\"#single\">One at a time</a></li>
+                            <li class=\"nav-item\"><a class=\"nav-link\" data-toggle=\"tab\" href=\"#batch\">Batch</a></li>
+                        </ul>
+                        <div class=\"tab-content\">
+                        <div class=\"tab-pane active\" id=\"single\">"
+    } else {
+        ""
+    };
     format!("<html lang=\"en\">
         <head>
             <meta charset=\"utf-8\">
             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1, shrink-to-fit=no\">
+            <title>{title}</title>
             <style>{bootstrap}</style>
             <script type=\"text/javascript\">
+                {send_message}
+                var next_id = 0;
+                var live = {live};
+                var live_timer = null;
+                function input_changed() {{
+                    if (live) {{
+                        clearTimeout(live_timer);
+                        live_timer = setTimeout(run_calculation, 250);
+                    }}
+                }}
+                window.addEventListener('load', () => {{
+                    if (live) {{
+                        var inputs = document.getElementById('input-group');
+                        ['input', 'change'].forEach(kind => inputs.addEventListener(kind, event => {{
+                            if (event.target.type !== 'file') {{
+                                input_changed();
+                            }}
+                        }}));
+                        input_changed();
+                    }}
+                }});
+                function show_error(error) {{
+                    var alert = document.getElementById('error');
+                    alert.textContent = error;
+                    alert.style.display = error === null ? 'none' : '';
+                }}
+                var running_since = null;
+                var running_timer = null;
+                function start_running() {{
+                    running_since = Date.now();
+                    clearInterval(running_timer);
+                    running_timer = setInterval(show_elapsed, 100);
+                }}
+                function stop_running() {{
+                    clearInterval(running_timer);
+                    document.getElementById('running').style.display = 'none';
+                }}
+                function show_elapsed() {{
+                    var elapsed = (Date.now() - running_since) / 1000;
+                    if (elapsed >= 0.3) {{
+                        document.getElementById('elapsed').textContent = elapsed.toFixed(1);
+                        document.getElementById('running').style.display = '';
+                    }}
+                }}
+                function cancel_calculation() {{
+                    send_message(JSON.stringify({{ version: {version}, id: next_id - 1, cancel: true }}));
+                    next_id++;
+                    stop_running();
+                }}
+                function handle_response(response) {{
+                    if (response.id < next_id - 1) {{
+                        return;
+                    }}
+                    if (!('partial' in response) && !('partial_error' in response)) {{
+                        stop_running();
+                    }}
+                    var outputs = document.getElementById('outputs');
+                    if ('partial_error' in response) {{
+                        show_error(response.partial_error);
+                    }} else if ('error' in response) {{
+                        Array.from(outputs.querySelectorAll('input, textarea')).forEach(x => x.value = '');
+                        show_error(response.error);
+                    }} else if ('partial' in response) {{
+                        outputs.innerHTML = response.partial.map(output => output.display).join('');
+                        show_error(null);
+                    }} else if ('batch' in response) {{
+                        outputs.innerHTML = response.batch.display;
+                        show_error(null);
+                    }} else {{
+                        outputs.innerHTML = response.outputs.map(output => output.display).join('');
+                        show_error(null);
+                    }}
+                }}
+                function load_image(zone, file) {{
+                    if (file === undefined) {{
+                        return;
+                    }}
+                    var image = new Image();
+                    image.onload = () => {{
+                        var canvas = zone.querySelector('canvas');
+                        var context = canvas.getContext('2d');
+                        context.clearRect(0, 0, canvas.width, canvas.height);
+                        context.drawImage(image, 0, 0, canvas.width, canvas.height);
+                        zone.dataset.loaded = 'true';
+                        input_changed();
+                        URL.revokeObjectURL(image.src);
+                    }};
+                    image.src = URL.createObjectURL(file);
+                }}
+                function clear_sketch(zone) {{
+                    var canvas = zone.querySelector('canvas');
+                    var context = canvas.getContext('2d');
+                    context.fillStyle = 'black';
+                    context.fillRect(0, 0, canvas.width, canvas.height);
+                }}
+                function sketch(event, canvas) {{
+                    var rect = canvas.getBoundingClientRect();
+                    var x = (event.clientX - rect.left) * canvas.width / rect.width;
+                    var y = (event.clientY - rect.top) * canvas.height / rect.height;
+                    var context = canvas.getContext('2d');
+                    if (event.type === 'pointerdown') {{
+                        canvas.setPointerCapture(event.pointerId);
+                        canvas.dataset.drawing = 'true';
+                        context.beginPath();
+                        context.moveTo(x, y);
+                    }} else if (event.type === 'pointerup') {{
+                        canvas.dataset.drawing = 'false';
+                        input_changed();
+                        return;
+                    }}
+                    if (canvas.dataset.drawing === 'true') {{
+                        context.strokeStyle = 'white';
+                        context.lineCap = 'round';
+                        context.lineJoin = 'round';
+                        context.lineWidth = Number(canvas.dataset.brush);
+                        context.lineTo(x, y);
+                        context.stroke();
+                    }}
+                }}
+                window.addEventListener('load', () => document.querySelectorAll('[data-kind=sketchpad]').forEach(clear_sketch));
+                function load_file(x) {{
+                    x.upload = null;
+                    var file = x.files[0];
+                    if (file === undefined) {{
+                        return;
+                    }}
+                    if (x.dataset.maxSize !== '' && file.size > Number(x.dataset.maxSize)) {{
+                        show_error(file.name + ' is too large: files can be at most ' + x.dataset.maxSize + ' bytes');
+                        x.value = '';
+                        return;
+                    }}
+                    var reader = new FileReader();
+                    reader.onload = () => {{
+                        var bytes = reader.result.slice(reader.result.indexOf(',') + 1);
+                        x.upload = {{ name: file.name, mime: file.type, bytes: bytes }};
+                        show_error(null);
+                        input_changed();
+                    }};
+                    reader.readAsDataURL(file);
+                }}
+                function to_base64(bytes) {{
+                    var binary = '';
+                    for (var i = 0; i < bytes.length; i += 0x8000) {{
+                        binary += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+                    }}
+                    return btoa(binary);
+                }}
+                function read_pixels(canvas) {{
+                    return to_base64(canvas.getContext('2d').getImageData(0, 0, canvas.width, canvas.height).data);
+                }}
+                function load_audio(zone, blob) {{
+                    if (blob === undefined) {{
+                        return;
+                    }}
+                    var rate = zone.dataset.sampleRate;
+                    var context = rate === '' ? new AudioContext() : new AudioContext({{ sampleRate: Number(rate) }});
+                    blob.arrayBuffer()
+                        .then(data => context.decodeAudioData(data))
+                        .then(buffer => {{
+                            var samples = new Float32Array(buffer.length);
+                            for (var c = 0; c < buffer.numberOfChannels; c++) {{
+                                var channel = buffer.getChannelData(c);
+                                for (var i = 0; i < buffer.length; i++) {{
+                                    samples[i] += channel[i] / buffer.numberOfChannels;
+                                }}
+                            }}
+                            zone.audio = {{ sample_rate: buffer.sampleRate, samples: to_base64(new Uint8Array(samples.buffer)) }};
+                            zone.querySelector('audio').src = URL.createObjectURL(blob);
+                            show_error(null);
+                            input_changed();
+                        }})
+                        .catch(error => show_error('Could not read the audio: ' + error))
+                        .finally(() => context.close());
+                }}
+                function record_audio(button) {{
+                    var zone = button.parentElement.parentElement;
+                    if (zone.recorder !== undefined) {{
+                        zone.recorder.stop();
+                        return;
+                    }}
+                    navigator.mediaDevices.getUserMedia({{ audio: true }})
+                        .then(stream => {{
+                            var chunks = [];
+                            var recorder = new MediaRecorder(stream);
+                            recorder.ondataavailable = event => chunks.push(event.data);
+                            recorder.onstop = () => {{
+                                stream.getTracks().forEach(track => track.stop());
+                                zone.recorder = undefined;
+                                button.textContent = 'Record';
+                                load_audio(zone, new Blob(chunks, {{ type: recorder.mimeType }}));
+                            }};
+                            zone.recorder = recorder;
+                            recorder.start();
+                            button.textContent = 'Stop';
+                        }})
+                        .catch(error => show_error('Could not record: ' + error));
+                }}
+                function read_input(x) {{
+                    switch (x.dataset.kind) {{
+                        case 'text':
+                            return x.value;
+                        case 'checkbox':
+                            return x.checked;
+                        case 'checkbox-group':
+                            return Array.from(x.querySelectorAll('input[type=checkbox]')).map(box => box.checked);
+                        case 'radio':
+                            const selected = x.querySelector('input:checked');
+                            return selected === null ? '' : Number(selected.value);
+                        case 'matrix':
+                            return Array.from(x.querySelector('tbody').rows)
+                                .map(row => Array.from(row.cells).map(cell => read_number(cell.querySelector('input').value)));
+                        case 'audio':
+                            return x.audio === undefined ? null : x.audio;
+                        case 'file':
+                            return x.upload === undefined ? null : x.upload;
+                        case 'sketchpad':
+                            return read_pixels(x.querySelector('canvas'));
+                        case 'image':
+                            return x.dataset.loaded === 'true' ? read_pixels(x.querySelector('canvas')) : '';
+                    }}
+                    return read_number(x.value);
+                }}
+                function read_number(x) {{
+                    return x.trim() === '' || isNaN(x) ? x : Number(x);
+                }}
+                function resize_matrix(button, rows, cols) {{
+                    var body = button.closest('[data-kind=matrix]').querySelector('tbody');
+                    if (rows > 0) {{
+                        var row = body.rows[body.rows.length - 1].cloneNode(true);
+                        row.querySelectorAll('input').forEach(x => x.value = '0');
+                        body.appendChild(row);
+                    }} else if (rows < 0 && body.rows.length > 1) {{
+                        body.deleteRow(-1);
+                    }}
+                    Array.from(body.rows).forEach(row => {{
+                        if (cols > 0) {{
+                            var cell = row.cells[row.cells.length - 1].cloneNode(true);
+                            cell.querySelector('input').value = '0';
+                            row.appendChild(cell);
+                        }} else if (cols < 0 && row.cells.length > 1) {{
+                            row.deleteCell(-1);
+                        }}
+                    }});
+                    input_changed();
+                }}
+                function sort_table(header) {{
+                    var body = header.closest('table').tBodies[0];
+                    var column = header.cellIndex;
+                    var ascending = header.dataset.order !== 'ascending';
+                    Array.from(header.parentElement.children).forEach(x => delete x.dataset.order);
+                    header.dataset.order = ascending ? 'ascending' : 'descending';
+                    var key = row => row.cells[column];
+                    Array.from(body.rows)
+                        .sort((a, b) => {{
+                            var x = key(a), y = key(b);
+                            var order = 'value' in x.dataset && 'value' in y.dataset
+                                ? Number(x.dataset.value) - Number(y.dataset.value)
+                                : x.textContent.localeCompare(y.textContent, undefined, {{ numeric: true }});
+                            return ascending ? order : -order;
+                        }})
+                        .forEach(row => body.appendChild(row));
+                }}
+                function copy_table(button) {{
+                    var table = button.parentElement.querySelector('table');
+                    var text = Array.from(table.rows)
+                        .map(row => Array.from(row.cells).map(cell => cell.textContent).join('\\t'))
+                        .join('\\n');
+                    var copied = () => button.textContent = 'Copied';
+                    if (navigator.clipboard !== undefined) {{
+                        navigator.clipboard.writeText(text).then(copied);
+                    }} else {{
+                        var area = document.createElement('textarea');
+                        area.value = text;
+                        document.body.appendChild(area);
+                        area.select();
+                        document.execCommand('copy');
+                        area.remove();
+                        copied();
+                    }}
+                }}
                 function run_calculation() {{
-                    var numbers = [];
+                    var inputs = {{}};
                     var classes = document.getElementsByClassName('input');
-                    Array.from(classes).forEach((x, i) => numbers.push(Number(x.value)));
-                    ipc.postMessage(numbers.toString());
+                    Array.from(classes).forEach(x => inputs[x.getAttribute('name')] = read_input(x));
+                    start_running();
+                    send_message(JSON.stringify({{ version: {version}, id: next_id++, inputs: inputs }}));
+                }}
+                function run_batch() {{
+                    var file = document.getElementById('batch-file').files[0];
+                    if (file === undefined) {{
+                        show_error('Choose a CSV file first');
+                        return;
+                    }}
+                    var reader = new FileReader();
+                    reader.onload = () => {{
+                        start_running();
+                        send_message(JSON.stringify({{ version: {version}, id: next_id++, inputs: {{}}, batch: reader.result }}));
+                    }};
+                    reader.readAsText(file);
                 }}
             </script>
         </head>
@@ -95,31 +904,58 @@ pub(crate) fn beginning(description: String) -> String {
                 <p class=\"mt-3 text-center\">{description}</p>
                 <div class=\"row my-3\">
                     <div class=\"col text-center bg-light mr-1\">
-                        <form class=\"m-3\" action=\"#\" method=\"POST\" onsubmit=\"run_calculation()\">
+                        {tabs}
+                        <form class=\"m-3\" action=\"#\" method=\"POST\" onsubmit=\"run_calculation(); return false;\">
                             <div class=\"form-group row\" id=\"input-group\">")
 }
 
-pub(crate) fn middle() -> String {
-    "                        </div>
+/// The part of the page between the inputs and the outputs
+pub(crate) fn middle(batch: bool, live: bool) -> String {
+    let batch_tab = if batch {
+        "</div>
+                        <div class=\"tab-pane\" id=\"batch\">
+                            <form class=\"m-3\" action=\"#\" method=\"POST\" onsubmit=\"run_batch(); return false;\">
+                                <label for=\"batch-file\" class=\"col-form-label\"><i>CSV file, with a column named after each input</i></label>
+                                <input type=\"file\" class=\"form-control-file\" id=\"batch-file\" accept=\".csv,text/csv\">
+                                <button type=\"submit\" class=\"btn btn-primary mt-3\">Run</button>
+                            </form>
+                        </div>
+                        </div>"
+    } else {
+        ""
+    };
+    let hidden = if live { "style=\"display: none\"" } else { "" };
+    format!(
+        "                        </div>
 
-                            <div class=\"form-group \" id=\"submit\">
+                            <div class=\"form-group \" id=\"submit\" {hidden}>
                                 <button type=\"submit\" class=\"btn btn-primary\">Submit</button>
                             </div>
                         </form>
+                        {batch_tab}
                     </div>
 
                     <div class=\"col bg-light ml-1\">
-                        <div class=\"form-group m-3\" id=\"output-group\">"
-        .to_string()
+                        <div class=\"form-group m-3\" id=\"output-group\">
+                            <div class=\"alert alert-info\" id=\"running\" role=\"status\" style=\"display: none\">
+                                <span class=\"spinner-border spinner-border-sm mr-2\"></span>
+                                Running for <span id=\"elapsed\">0.0</span> s
+                                <button type=\"button\" class=\"btn btn-sm btn-outline-secondary ml-2\" onclick=\"cancel_calculation()\">Cancel</button>
+                            </div>
+                            <div id=\"outputs\">"
+    )
 }
 
+/// The end of the page, after the outputs
 pub(crate) fn end() -> String {
     let jquery = include_str!("bootstrap/jquery-3.3.1.slim.min.js");
     let popper = include_str!("bootstrap/popper.min.js");
     let bootstrap = include_str!("bootstrap/bootstrap.min.js");
 
     format!(
-        "                    </div>
+        "                            </div>
+                            <div class=\"alert alert-danger mt-3\" id=\"error\" role=\"alert\" style=\"display: none\"></div>
+                        </div>
                     </div>
                 </div>
             </div>
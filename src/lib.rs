@@ -5,27 +5,58 @@
 #![doc = include_str!("../README.md")]
 
 use std::{
-    cell::RefCell,
-    collections::HashMap,
-    fmt::{Debug, Display},
-    str::FromStr,
-};
-
-use wry::{
-    application::{
-        event::{Event, StartCause, WindowEvent},
-        event_loop::{ControlFlow, EventLoop},
-        window::{Window, WindowBuilder},
-    },
-    webview::{WebView, WebViewAttributes, WebViewBuilder},
+    any::Any,
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    sync::Arc,
 };
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use num_traits::{zero, Float};
 
+#[cfg(feature = "server")]
+mod api;
+
+mod batch;
+
 pub mod cookbook;
 
+/// The pieces of HTML the page is assembled from
 mod html_chunks;
-use html_chunks::{add_dropdown, add_number, add_slider, beginning, end, middle};
+use html_chunks::{
+    add_audio, add_audio_output, add_checkbox, add_checkbox_group, add_dropdown, add_file,
+    add_image, add_image_output, add_label_output, add_matrix, add_number, add_number_output,
+    add_plot_output, add_radio, add_sketchpad, add_slider, add_table_output, add_text,
+    add_text_output, beginning, end, middle, Transport,
+};
+
+pub mod protocol;
+use protocol::{OutputValue, Request, Response};
+
+mod pixels;
+pub use pixels::{ColorMode, Image, PixelLayout};
+
+mod plot;
+pub use plot::PlotKind;
+
+#[cfg(feature = "server")]
+mod server;
+
+mod stream;
+pub use stream::Emitter;
+
+#[doc(hidden)]
+pub mod wasm;
+
+#[cfg(feature = "window")]
+mod window;
+
+mod value;
+pub use value::{Audio, FromInputs, FromValue, IntoValue, IntoValues, Kind, Upload, Value};
 
 /// Types of inputs for the model
 #[derive(Clone)]
@@ -38,14 +69,18 @@ pub enum Input<F: Float + Display = f32> {
         /// Initial value to shown in the textbox
         initial_value: F,
     },
-    // /// A textual input
-    // TextBox {
-    //     /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
-    //     label: Option<String>,
-    //     /// Initial value to shown in the textbox
-    //     initial_value: String,
-    // },
-    /// A slider input
+    /// A textual input, for use with a function added using `with_value_function`
+    Text {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Initial value to shown in the textbox
+        initial_value: String,
+        /// Whether to show a text area with room for several lines, instead of a single line
+        multiline: bool,
+        /// Hint to show in the textbox while it is empty
+        placeholder: Option<String>,
+    },
+    /// A slider input, which only takes values between its minimum and maximum
     Slider {
         /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
         label: Option<String>,
@@ -62,38 +97,387 @@ pub enum Input<F: Float + Display = f32> {
     Dropdown {
         /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
         label: Option<String>,
-        /// Set of options to include in the dropdown
-        options: Vec<F>,
-        /// Initial value to show for the dropdown
+        /// Set of options to include in the dropdown, each with the text shown in the dropdown and
+        /// the value passed to the function when it is selected
+        options: Vec<(String, F)>,
+        /// Index of the option that starts out selected
         initial_value: usize,
+        /// Whether to pass the function a 1 or a 0 for each option (a one-hot encoding of the
+        /// selection) instead of the value of the selected option
+        one_hot: bool,
+    },
+    /// A radio button selector, which shows every option at once
+    Radio {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Set of options to include in the selection, each with the text shown beside its button
+        /// and the value passed to the function when it is selected
+        options: Vec<(String, F)>,
+        /// Index of the option that starts out selected
+        initial_value: usize,
+    },
+    /// A checkbox, which is passed to the function as 1 when checked and 0 when not (or as a
+    /// `bool`, for a function added using `with_typed_function`)
+    Checkbox {
+        /// Label to be shown beside the checkbox. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Whether the checkbox starts out checked
+        initial_value: bool,
+    },
+    /// A group of checkboxes, which is passed to the function as a 1 or a 0 for each option (or as
+    /// a `Vec<bool>`, for a function added using `with_typed_function`)
+    CheckboxGroup {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Label for each checkbox in the group
+        options: Vec<String>,
+        /// Indices of the options that start out checked
+        initial_selected: Vec<usize>,
+    },
+    /// An image, picked from a file or dropped onto the input, which is stretched to `width` by
+    /// `height` pixels and passed to the function as values between 0 and 1 (a `Vec<f32>`, for a
+    /// function added using `with_typed_function`)
+    Image {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Width that the image is resized to, in pixels
+        width: usize,
+        /// Height that the image is resized to, in pixels
+        height: usize,
+        /// Channels kept from each pixel
+        color_mode: ColorMode,
+        /// Order in which the values of the image are passed to the function
+        layout: PixelLayout,
+    },
+    /// A canvas to draw on, which is passed to the function as a `width` by `height` grid of
+    /// values between 0 (blank) and 1 (drawn on), row by row
+    Sketchpad {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Width of the grid, in pixels
+        width: usize,
+        /// Height of the grid, in pixels
+        height: usize,
+        /// Width of the brush, in pixels of the grid
+        brush_size: usize,
+    },
+    /// A grid of numbers, which is passed to the function row by row (or as a `Vec<Vec<f64>>`,
+    /// for a function added using `with_typed_function`)
+    Matrix {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Number of rows in the grid
+        rows: usize,
+        /// Number of columns in the grid
+        cols: usize,
+        /// Initial values of the grid, row by row. Missing values start out as zero.
+        initial_values: Vec<Vec<F>>,
+        /// Whether the user can add and remove rows and columns
+        editable_dims: bool,
     },
-    // Checkbox,
-    // CheckboxGroup,
-    // Image,
     // Video,
-    // Audio,
-    // File,
-    // /// A radio button selector
-    // Radio {
-    //     /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
-    //     label: Option<String>,
-    //     /// Set of options to include in the selection
-    //     options: Vec<f64>,
-    // },
-    // /// An area to upload a file. Specifying a file type will add a preview of the file after loading.
-    // File {
-    //     /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
-    //     label: Option<String>,
-    // },
+    /// A recording, uploaded from a file or recorded with the microphone, which is passed to a
+    /// function added using `with_value_function` or `with_typed_function` as an [`Audio`] with
+    /// the channels mixed down to one
+    Audio {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Number of samples per second to resample the recording to. If `None`, the recording
+        /// keeps the sample rate of the audio device.
+        sample_rate: Option<u32>,
+    },
+    /// An area to upload a file, which is passed to the function as an [`Upload`] holding its
+    /// name, type and contents, for use with a function added using `with_value_function` or
+    /// `with_typed_function`
+    File {
+        /// Label to be shown above input. If value is `None`, a default of the form _Input N_ will be shown.
+        label: Option<String>,
+        /// Extensions of the files that can be uploaded (e.g. `".csv"`). If empty, any file can be.
+        extensions: Vec<String>,
+        /// Largest file that can be uploaded, in bytes. If `None`, files of any size can be.
+        max_size: Option<usize>,
+    },
 }
 
 impl<F: Float + Display> Input<F> {
+    /// The label shown above the input, falling back on _Input N_
+    fn get_label(&self, idx: usize) -> String {
+        let label = match self {
+            Input::Number { label, .. } => label,
+            Input::Text { label, .. } => label,
+            Input::Slider { label, .. } => label,
+            Input::Dropdown { label, .. } => label,
+            Input::Radio { label, .. } => label,
+            Input::Checkbox { label, .. } => label,
+            Input::CheckboxGroup { label, .. } => label,
+            Input::Image { label, .. } => label,
+            Input::Sketchpad { label, .. } => label,
+            Input::File { label, .. } => label,
+            Input::Audio { label, .. } => label,
+            Input::Matrix { label, .. } => label,
+        };
+        match label {
+            None => format!("Input {idx}"),
+            Some(string) => string.to_string(),
+        }
+    }
+
+    /// The kind of value that the input produces
+    fn kind(&self) -> Kind {
+        match self {
+            Input::Text { .. } => Kind::Text,
+            Input::Checkbox { .. } => Kind::Bool,
+            Input::CheckboxGroup { .. } => Kind::List,
+            Input::Dropdown { one_hot: true, .. } => Kind::List,
+            Input::Image { .. } => Kind::List,
+            Input::Sketchpad { .. } => Kind::List,
+            Input::File { .. } => Kind::File,
+            Input::Audio { .. } => Kind::Audio,
+            Input::Matrix { .. } => Kind::List,
+            _ => Kind::Float,
+        }
+    }
+
+    /// Read the value of the `idx`th input out of a request
+    fn read(&self, idx: usize, value: &serde_json::Value) -> Result<Value, String> {
+        let mismatch = |kind: &str| format!("{} must be {kind}, not {value}", self.get_label(idx));
+        match self {
+            Input::Text { .. } => match value {
+                serde_json::Value::String(text) => Ok(Value::Text(text.clone())),
+                _ => Err(mismatch("text")),
+            },
+            Input::Checkbox { .. } => read_bool(value)
+                .map(Value::Bool)
+                .ok_or_else(|| mismatch("checked or unchecked")),
+            Input::CheckboxGroup { options, .. } => match value {
+                serde_json::Value::Array(checks) if checks.len() == options.len() => checks
+                    .iter()
+                    .map(|check| read_bool(check).map(Value::Bool))
+                    .collect::<Option<_>>()
+                    .map(Value::List)
+                    .ok_or_else(|| mismatch("checked or unchecked for each option")),
+                _ => Err(mismatch("checked or unchecked for each option")),
+            },
+            Input::Dropdown {
+                options, one_hot, ..
+            } => match read_choice(options, value) {
+                Some(choice) if *one_hot => Ok(Value::List(
+                    (0..options.len())
+                        .map(|idx| Value::Float(if idx == choice { 1.0 } else { 0.0 }))
+                        .collect(),
+                )),
+                Some(choice) => Ok(Value::from_float(options[choice].1)),
+                None => Err(mismatch("one of the options")),
+            },
+            Input::Image {
+                width,
+                height,
+                color_mode,
+                layout,
+                ..
+            } => match value {
+                serde_json::Value::String(encoded) if !encoded.is_empty() => {
+                    pixels::decode(encoded, *width, *height, *color_mode, *layout).map_err(
+                        |error| format!("{} could not be read: {error}", self.get_label(idx)),
+                    )
+                }
+                _ => Err(mismatch("an image")),
+            },
+            Input::Sketchpad { width, height, .. } => match value {
+                serde_json::Value::String(encoded) => pixels::decode(
+                    encoded,
+                    *width,
+                    *height,
+                    ColorMode::Grayscale,
+                    PixelLayout::Hwc,
+                )
+                .map_err(|error| format!("{} could not be read: {error}", self.get_label(idx))),
+                _ => Err(mismatch("a drawing")),
+            },
+            Input::File {
+                extensions,
+                max_size,
+                ..
+            } => {
+                let upload = read_upload(value).ok_or_else(|| mismatch("a file"))?;
+                let label = self.get_label(idx);
+                if !extensions.is_empty()
+                    && !extensions.iter().any(|extension| {
+                        upload
+                            .name
+                            .to_lowercase()
+                            .ends_with(&extension.to_lowercase())
+                    })
+                {
+                    return Err(format!(
+                        "{label} must be a {} file, not {}",
+                        extensions.join(" or "),
+                        upload.name
+                    ));
+                }
+                match max_size {
+                    Some(max_size) if upload.bytes.len() > *max_size => Err(format!(
+                        "{label} must be at most {max_size} bytes, but {} is {} bytes",
+                        upload.name,
+                        upload.bytes.len()
+                    )),
+                    _ => Ok(Value::File(upload)),
+                }
+            }
+            Input::Matrix {
+                rows,
+                cols,
+                editable_dims,
+                ..
+            } => {
+                let grid = value
+                    .as_array()
+                    .and_then(|grid| {
+                        grid.iter()
+                            .map(|row| row.as_array()?.iter().map(read_number).collect())
+                            .collect::<Option<Vec<Vec<f64>>>>()
+                    })
+                    .ok_or_else(|| mismatch("a grid of numbers"))?;
+                let width = grid.first().map_or(0, Vec::len);
+                if grid.iter().any(|row| row.len() != width) {
+                    return Err(mismatch("a grid of numbers, with as many in every row"));
+                }
+                if !editable_dims && (grid.len() != *rows || width != *cols) {
+                    return Err(mismatch(&format!("a {rows}x{cols} grid of numbers")));
+                }
+                Ok(Value::List(
+                    grid.into_iter()
+                        .map(|row| Value::List(row.into_iter().map(Value::Float).collect()))
+                        .collect(),
+                ))
+            }
+            Input::Audio { .. } => read_audio(value)
+                .map(Value::Audio)
+                .ok_or_else(|| mismatch("audio")),
+            Input::Radio { options, .. } => read_choice(options, value)
+                .map(|choice| Value::from_float(options[choice].1))
+                .ok_or_else(|| mismatch("one of the options")),
+            Input::Slider { min, max, .. } => read_number(value)
+                .filter(|x| F::from(*x).is_some_and(|x| *min <= x && x <= *max))
+                .map(Value::Float)
+                .ok_or_else(|| mismatch(&format!("a number from {min} to {max}"))),
+            _ => read_number(value)
+                .map(Value::Float)
+                .ok_or_else(|| mismatch("a number")),
+        }
+    }
+
+    /// A copy of the input that shows `value` instead of its initial value
+    fn with_value(&self, value: &Value) -> Self {
+        let mut input = self.clone();
+        match (&mut input, value) {
+            (Input::Number { initial_value, .. }, value) => {
+                *initial_value = value.to_float().unwrap_or(*initial_value)
+            }
+            (Input::Text { initial_value, .. }, value) => *initial_value = value.to_string(),
+            (Input::Slider { initial_value, .. }, value) => {
+                *initial_value = value.to_float().unwrap_or(*initial_value)
+            }
+            (
+                Input::Dropdown {
+                    initial_value,
+                    one_hot: true,
+                    ..
+                },
+                Value::List(hot),
+            ) => {
+                if let Some(idx) = hot.iter().position(|x| x.as_float() == Some(1.0)) {
+                    *initial_value = idx;
+                }
+            }
+            (
+                Input::Dropdown {
+                    options,
+                    initial_value,
+                    ..
+                },
+                value,
+            ) => {
+                if let Some(idx) = options
+                    .iter()
+                    .position(|(_, option)| Some(*option) == value.to_float())
+                {
+                    *initial_value = idx;
+                }
+            }
+            (
+                Input::Radio {
+                    options,
+                    initial_value,
+                    ..
+                },
+                value,
+            ) => {
+                if let Some(idx) = options
+                    .iter()
+                    .position(|(_, option)| Some(*option) == value.to_float())
+                {
+                    *initial_value = idx;
+                }
+            }
+            (Input::Checkbox { initial_value, .. }, value) => {
+                *initial_value = value.as_bool().unwrap_or(*initial_value)
+            }
+            (
+                Input::CheckboxGroup {
+                    initial_selected, ..
+                },
+                Value::List(checks),
+            ) => {
+                *initial_selected = (0..checks.len())
+                    .filter(|idx| checks[*idx].as_bool() == Some(true))
+                    .collect()
+            }
+            (Input::CheckboxGroup { .. }, _) => {}
+            (Input::Image { .. }, _) => {}
+            (Input::Sketchpad { .. }, _) => {}
+            (Input::File { .. }, _) => {}
+            (Input::Audio { .. }, _) => {}
+            (
+                Input::Matrix {
+                    rows,
+                    cols,
+                    initial_values,
+                    ..
+                },
+                Value::List(grid),
+            ) => {
+                *initial_values = grid
+                    .iter()
+                    .map(|row| {
+                        row.as_list()
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|x| x.to_float().unwrap_or_else(zero))
+                            .collect()
+                    })
+                    .collect();
+                *rows = initial_values.len();
+                *cols = initial_values.first().map_or(0, Vec::len);
+            }
+            (Input::Matrix { .. }, _) => {}
+        }
+        input
+    }
+
+    /// The HTML for this input, placed at position `idx` on the page
     fn get_html(&self, idx: usize) -> String {
         match self {
             Input::Number {
                 initial_value,
                 label,
             } => add_number(idx, initial_value, label),
+            Input::Text {
+                initial_value,
+                multiline,
+                placeholder,
+                label,
+            } => add_text(idx, initial_value, *multiline, placeholder, label),
             Input::Slider {
                 min,
                 max,
@@ -105,16 +489,47 @@ impl<F: Float + Display> Input<F> {
                 initial_value,
                 options,
                 label,
+                ..
             } => add_dropdown(idx, initial_value, options, label),
-            // Input::File { label, filetype } => match filetype {
-            //     Some(ft) => match ft {
-            //         TypeOfFile::Image => "".to_string(),
-            //         TypeOfFile::Audio => "".to_string(),
-            //         TypeOfFile::Model => "".to_string(),
-            //         TypeOfFile::CSV => "".to_string(),
-            //     },
-            //     None => "".to_string(),
-            // },
+            Input::Radio {
+                initial_value,
+                options,
+                label,
+            } => add_radio(idx, *initial_value, options, label),
+            Input::Checkbox {
+                initial_value,
+                label,
+            } => add_checkbox(idx, *initial_value, label),
+            Input::CheckboxGroup {
+                options,
+                initial_selected,
+                label,
+            } => add_checkbox_group(idx, options, initial_selected, label),
+            Input::Image {
+                width,
+                height,
+                label,
+                ..
+            } => add_image(idx, *width, *height, label),
+            Input::Sketchpad {
+                width,
+                height,
+                brush_size,
+                label,
+            } => add_sketchpad(idx, *width, *height, *brush_size, label),
+            Input::File {
+                extensions,
+                max_size,
+                label,
+            } => add_file(idx, extensions, *max_size, label),
+            Input::Audio { sample_rate, label } => add_audio(idx, *sample_rate, label),
+            Input::Matrix {
+                rows,
+                cols,
+                initial_values,
+                editable_dims,
+                label,
+            } => add_matrix(idx, *rows, *cols, initial_values, *editable_dims, label),
         }
     }
 }
@@ -136,14 +551,59 @@ pub enum Output {
     Number {
         /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
         label: Option<String>,
+        /// Number of digits to show after the decimal point
         precision: usize,
     },
+    /// A textual output
+    Text {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+    },
+    /// The class predicted by a classifier. Given a list of probabilities (one for each class), it
+    /// shows the most likely class along with a ranking of the most likely classes. Given a single
+    /// number, it shows the class with that number.
+    Label {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+        /// Name of each class, in order. Classes without a name are shown as _Class N_.
+        class_names: Vec<String>,
+        /// Number of classes to include in the ranking
+        top_k: usize,
+    },
+    /// A recording, which can be played back in the GUI
+    Audio {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+    },
+    /// A plot of one or more series of points, given as a list of `(x, y)` pairs (or of numbers,
+    /// which are spaced one apart), or as a list of such lists
+    Plot {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+        /// How the points are drawn
+        kind: PlotKind,
+        /// Label to be shown below the horizontal axis
+        x_label: Option<String>,
+        /// Label to be shown beside the vertical axis
+        y_label: Option<String>,
+    },
+    /// A table, given as a list of rows, each of which is a list of values (like a `Vec` of tuples).
+    /// The table can be sorted by clicking on its headers, and copied to the clipboard.
+    Table {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+        /// Header of each column. Columns without a header are shown as _Column N_.
+        columns: Vec<String>,
+    },
+    /// An image, given either as an [`Image`] or as the bytes of a PNG or JPEG file (in a
+    /// [`Value::Bytes`])
+    Image {
+        /// Label to be shown above output. If value is `None`, a default of the form _Result N_ will be shown.
+        label: Option<String>,
+    },
     // Vector {
     //     label: Option<String>,
     // },
-    // Text {
-    //     label: Option<String>,
-    // },
     // File {
     //     label: Option<String>,
     //     filetype: TypeOfFile,
@@ -163,35 +623,273 @@ impl Default for Output {
 }
 
 impl Output {
-    fn get_html(&self) -> String {
+    /// The label shown above the output, falling back on _Result N_
+    fn get_label(&self, idx: usize) -> String {
         let label = match self {
-            Output::Number { label, .. } => match label {
-                None => {
-                    format!("Result")
+            Output::Number { label, .. } => label,
+            Output::Text { label } => label,
+            Output::Label { label, .. } => label,
+            Output::Audio { label } => label,
+            Output::Image { label } => label,
+            Output::Plot { label, .. } => label,
+            Output::Table { label, .. } => label,
+        };
+        match label {
+            None => format!("Result {idx}"),
+            Some(string) => string.to_string(),
+        }
+    }
+
+    /// The output as it should be shown in the GUI, displaying `value` (or nothing, if there is
+    /// no value yet)
+    fn get_html(&self, idx: usize, value: Option<&Value>) -> Result<String, String> {
+        let label = self.get_label(idx);
+        let mismatch = |kind: &str, value: &Value| format!("{label} must be {kind}, not {value}");
+        match self {
+            Output::Number { precision, .. } => {
+                let text = match value {
+                    None => String::new(),
+                    Some(y) => match y.as_float() {
+                        Some(y) => format!("{y:.precision$}"),
+                        None => return Err(mismatch("a number", y)),
+                    },
+                };
+                Ok(add_number_output(idx, &label, &text))
+            }
+            Output::Text { .. } => {
+                let text = value.map(Value::to_string).unwrap_or_default();
+                Ok(add_text_output(idx, &label, &text))
+            }
+            Output::Label { .. } => {
+                let (predicted, ranked) = match value {
+                    None => (String::new(), vec![]),
+                    Some(y) => self.rank_classes(idx, y)?,
+                };
+                Ok(add_label_output(idx, &label, &predicted, &ranked))
+            }
+            Output::Audio { .. } => {
+                let wav = match value {
+                    None => String::new(),
+                    Some(Value::Audio(audio)) => STANDARD.encode(
+                        audio
+                            .to_wav()
+                            .map_err(|error| format!("{label} is {error}"))?,
+                    ),
+                    Some(y) => return Err(mismatch("audio", y)),
+                };
+                Ok(add_audio_output(idx, &label, &wav))
+            }
+            Output::Image { .. } => {
+                let src = match value {
+                    None => String::new(),
+                    Some(Value::Image(image)) => format!(
+                        "data:image/png;base64,{}",
+                        STANDARD.encode(
+                            image
+                                .to_png()
+                                .map_err(|error| format!("{label} is {error}"))?
+                        )
+                    ),
+                    Some(Value::Bytes(bytes)) if bytes.starts_with(b"\x89PNG") => {
+                        format!("data:image/png;base64,{}", STANDARD.encode(bytes))
+                    }
+                    Some(Value::Bytes(bytes)) if bytes.starts_with(&[0xff, 0xd8, 0xff]) => {
+                        format!("data:image/jpeg;base64,{}", STANDARD.encode(bytes))
+                    }
+                    Some(y) => return Err(mismatch("an image", y)),
+                };
+                Ok(add_image_output(idx, &label, &src))
+            }
+            Output::Plot {
+                kind,
+                x_label,
+                y_label,
+                ..
+            } => {
+                let svg = match value {
+                    None => String::new(),
+                    Some(y) => match plot::read_series(y) {
+                        Some(series) => plot::render_svg(*kind, &series, x_label, y_label),
+                        None => return Err(mismatch("a list of points", y)),
+                    },
+                };
+                Ok(add_plot_output(idx, &label, &svg))
+            }
+            Output::Table { columns, .. } => {
+                let rows = match value {
+                    None => vec![],
+                    Some(Value::List(rows)) => rows
+                        .iter()
+                        .map(|row| match row {
+                            Value::List(cells) => cells.clone(),
+                            cell => vec![cell.clone()],
+                        })
+                        .collect(),
+                    Some(y) => return Err(mismatch("a list of rows", y)),
+                };
+                let width = rows
+                    .iter()
+                    .map(Vec::len)
+                    .max()
+                    .unwrap_or(0)
+                    .max(columns.len());
+                let columns = (0..width)
+                    .map(|column| match columns.get(column) {
+                        Some(name) => name.clone(),
+                        None => format!("Column {column}"),
+                    })
+                    .collect::<Vec<_>>();
+                Ok(add_table_output(idx, &label, &columns, &rows))
+            }
+        }
+    }
+
+    /// The predicted class shown by a label output, along with the most likely classes and their
+    /// probabilities (if `y` holds probabilities rather than a class number)
+    fn rank_classes(&self, idx: usize, y: &Value) -> Result<(String, Vec<(String, f64)>), String> {
+        let (class_names, top_k) = match self {
+            Output::Label {
+                class_names, top_k, ..
+            } => (class_names, *top_k),
+            _ => return Ok((y.to_string(), vec![])),
+        };
+        let mismatch = |kind: &str, value: &Value| {
+            format!("{} must be {kind}, not {value}", self.get_label(idx))
+        };
+        let class_name = |class: usize| match class_names.get(class) {
+            Some(name) => name.to_string(),
+            None => format!("Class {class}"),
+        };
+        match y {
+            Value::List(probabilities) => {
+                let mut ranked = vec![];
+                for (class, probability) in probabilities.iter().enumerate() {
+                    match probability.as_float() {
+                        Some(p) => ranked.push((class_name(class), p)),
+                        None => return Err(mismatch("a list of probabilities", probability)),
+                    }
                 }
-                Some(string) => string.to_string(),
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+                let predicted = ranked
+                    .first()
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default();
+                ranked.truncate(top_k);
+                Ok((predicted, ranked))
+            }
+            y => match y.as_int().and_then(|class| usize::try_from(class).ok()) {
+                Some(class) => Ok((class_name(class), vec![])),
+                None => Err(mismatch("a class number or a list of probabilities", y)),
             },
-        };
-        format!("<label for=\"output\" class=\"col-form-label mt-3\"><i>{label}</i></label>
-         <input type=\"text\" class=\"form-control\" id=\"output\" name=\"output\" aria-describedby=\"output\" readonly>")
+        }
     }
 
-    fn get_precision(&self) -> usize {
+    /// The output as plain text, for a cell of a table
+    fn get_text(&self, idx: usize, y: &Value) -> Result<String, String> {
         match self {
-            Output::Number { precision, .. } => *precision,
+            Output::Number { precision, .. } => match y.as_float() {
+                Some(x) => Ok(format!("{x:.precision$}")),
+                None => Err(format!("{} must be a number, not {y}", self.get_label(idx))),
+            },
+            Output::Label { .. } => Ok(self.rank_classes(idx, y)?.0),
+            _ => Ok(y.to_string()),
         }
     }
+
+    /// Package a result of the function for the page, as the `idx`th output
+    fn render(&self, idx: usize, y: &Value) -> Result<OutputValue, String> {
+        Ok(OutputValue {
+            value: y.to_json(),
+            display: self.get_html(idx, Some(y))?,
+        })
+    }
+}
+
+/// The values returned by a function added using `with_multi_function` or `with_value_function`.
+/// A `Vec`, an array or a tuple gives one value for each output. (A function added using
+/// `with_typed_function` returns [`IntoValues`] instead, where a `Vec` is a single output holding
+/// a list.)
+pub trait IntoOutputs<F> {
+    /// One value for each output, in order, or an error to show instead
+    fn into_outputs(self) -> Result<Vec<F>, String>;
+}
+
+impl<F> IntoOutputs<F> for Vec<F> {
+    fn into_outputs(self) -> Result<Vec<F>, String> {
+        Ok(self)
+    }
 }
 
+impl<F, const N: usize> IntoOutputs<F> for [F; N] {
+    fn into_outputs(self) -> Result<Vec<F>, String> {
+        Ok(self.into())
+    }
+}
+
+/// Implement [`IntoOutputs`] for a tuple whose every element is `F`
+macro_rules! impl_into_outputs_for_tuple {
+    ($($y:ident),+) => {
+        impl<F> IntoOutputs<F> for ($(impl_into_outputs_for_tuple!(@float $y),)+) {
+            fn into_outputs(self) -> Result<Vec<F>, String> {
+                let ($($y,)+) = self;
+                Ok(vec![$($y),+])
+            }
+        }
+    };
+    (@float $y:ident) => { F };
+}
+
+impl_into_outputs_for_tuple!(a, b);
+impl_into_outputs_for_tuple!(a, b, c);
+impl_into_outputs_for_tuple!(a, b, c, d);
+impl_into_outputs_for_tuple!(a, b, c, d, e);
+impl_into_outputs_for_tuple!(a, b, c, d, e, f);
+
+impl<F, R: IntoOutputs<F>, E: Display> IntoOutputs<F> for Result<R, E> {
+    fn into_outputs(self) -> Result<Vec<F>, String> {
+        self.map_err(|error| error.to_string())?.into_outputs()
+    }
+}
+
+/// A function from the value of each input to the value of each output, or an error to show
+type Function = Box<dyn 'static + Fn(Vec<Value>) -> Result<Vec<Value>, String>>;
+
+/// A function that is `Send` and `Sync`, so that it can be run on a worker thread, along with
+/// an [`Emitter`] for any outputs it has to show before it is done
+type BackgroundFunction =
+    Arc<dyn 'static + Send + Sync + Fn(Vec<Value>, &Emitter) -> Result<Vec<Value>, String>>;
+
+/// A check, made at startup, that the function can take the kinds of values the inputs produce
+type ArgumentCheck = fn(&[Kind]) -> Result<(), String>;
+
+/// A function that is handed each input as it stands along with its value, and decides on its
+/// own outputs
+type AdvancedFunction<F> = Box<dyn 'static + Fn(Vec<(Input<F>, Value)>) -> Vec<(Output, Value)>>;
+
 /// Construct a teaser to demonstrate your model
 pub struct Teaser<F: Float + Display = f32> {
+    /// Title of the window, and heading of the page
     title: String,
+    /// Text shown below the title
     description: String,
+    /// Inputs, in the order their values are passed to the function
     inputs: Vec<Input<F>>,
-    output: Output,
-    function: Box<dyn 'static + Fn(Vec<F>) -> F>,
+    /// Outputs, in the order the function returns their values
+    outputs: Vec<Output>,
+    /// Function run on the values of the inputs
+    function: Function,
+    /// Version of `function` to run on a worker thread instead, if it was added as one
+    background_function: Option<BackgroundFunction>,
+    /// Check that `function` can take the kinds of values the inputs produce
+    argument_check: ArgumentCheck,
+    /// Whether `advanced_function` is run in place of `function`
     use_advanced_function: bool,
-    advanced_function: Box<dyn 'static + Fn(Vec<Input>) -> Vec<Output>>,
+    /// Function that decides on its own outputs, run if `use_advanced_function`
+    advanced_function: AdvancedFunction<F>,
+    /// Whether the function runs whenever an input changes, rather than on submit
+    live: bool,
+    /// WebAssembly module embedded in the exported page, so that it runs the function itself
+    wasm_module: Option<Vec<u8>>,
 }
 
 impl<F: Float + Display> Default for Teaser<F> {
@@ -200,15 +898,19 @@ impl<F: Float + Display> Default for Teaser<F> {
             title: "Demo".to_string(),
             description: "".to_string(),
             inputs: vec![Input::default()],
-            output: Output::default(),
-            function: Box::new(|_| zero()),
+            outputs: vec![Output::default()],
+            function: Box::new(|_| Ok(vec![Value::Float(0.0)])),
+            background_function: None,
+            argument_check: |_| Ok(()),
             use_advanced_function: false,
-            advanced_function: Box::new(|_| vec![Output::default()]),
+            advanced_function: Box::new(|_| vec![(Output::default(), Value::Float(0.0))]),
+            live: false,
+            wasm_module: None,
         }
     }
 }
 
-impl<F: 'static + Float + Display + FromStr> Teaser<F> {
+impl<F: 'static + Float + Display> Teaser<F> {
     /// Add a title to the GUI
     pub fn with_title(mut self, title: String) -> Self {
         self.title = title;
@@ -221,15 +923,51 @@ impl<F: 'static + Float + Display + FromStr> Teaser<F> {
         self
     }
 
+    /// Rerun the function whenever an input changes, instead of waiting for the user to press
+    /// <kbd>Submit</kbd> (which is hidden). Changes are collected for a moment before the function
+    /// is run, so scrubbing a slider doesn't queue up a run for every step, and results that arrive
+    /// after a newer run was started are dropped.
+    /// ```rust, no_run
+    /// use tease::{Input, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::Slider {
+    ///         label: Some("Angle".to_string()),
+    ///         min: 0.0,
+    ///         max: 6.28,
+    ///         step: 0.01,
+    ///         initial_value: 0.0,
+    ///     }])
+    ///     .with_function(|x: Vec<f32>| x[0].sin())
+    ///     .with_live(true)
+    ///     .run();
+    /// ```
+    pub fn with_live(mut self, live: bool) -> Self {
+        self.live = live;
+        self
+    }
+
+    /// Embed a WebAssembly module, built with [`export_wasm`], in the page written by
+    /// [`export_html`](Teaser::export_html), so that the page can run the function on its own
+    pub fn with_wasm_module(mut self, module: Vec<u8>) -> Self {
+        self.wasm_module = Some(module);
+        self
+    }
+
     /// Specify the inputs
     pub fn with_inputs(mut self, inputs: Vec<Input<F>>) -> Self {
         self.inputs = inputs;
         self
     }
 
-    /// Specify the inputs
+    /// Specify the output
     pub fn with_output(mut self, output: Output) -> Self {
-        self.output = output;
+        self.outputs = vec![output];
+        self
+    }
+
+    /// Specify several outputs, to be filled in by a function added using `with_multi_function`
+    pub fn with_outputs(mut self, outputs: Vec<Output>) -> Self {
+        self.outputs = outputs;
         self
     }
 
@@ -238,88 +976,700 @@ impl<F: 'static + Float + Display + FromStr> Teaser<F> {
     where
         G: 'static + Fn(Vec<F>) -> F,
     {
-        self.function = Box::new(predictor);
+        self.function = Box::new(move |x| Ok(vec![Value::from_float(predictor(floats(x)?))]));
+        self.background_function = None;
+        self.argument_check = check_floats;
+        self
+    }
+
+    /// Specify a function that can fail. Any error it returns is shown in the GUI in place of the
+    /// output, and the GUI stays open for another try.
+    /// ```rust, no_run
+    /// use tease::{Input, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::default(); 2])
+    ///     .with_fallible_function(|x: Vec<f64>| {
+    ///         if x[1] == 0.0 {
+    ///             Err("Cannot divide by zero")
+    ///         } else {
+    ///             Ok(x[0] / x[1])
+    ///         }
+    ///     })
+    ///     .run();
+    /// ```
+    pub fn with_fallible_function<G, E>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Fn(Vec<F>) -> Result<F, E>,
+        E: Display,
+    {
+        self.function = Box::new(move |x| {
+            predictor(floats(x)?)
+                .map(|y| vec![Value::from_float(y)])
+                .map_err(|error| error.to_string())
+        });
+        self.background_function = None;
+        self.argument_check = check_floats;
+        self
+    }
+
+    /// Specify a function with several outputs. It can return a `Vec`, an array or a tuple, with
+    /// one value for each of the outputs given to `with_outputs`, or a `Result` of any of these.
+    /// Note that a `Vec` returned by a function added using `with_typed_function` means something
+    /// else: there it is a single output holding a list, and only a tuple spreads over the outputs.
+    /// ```rust, no_run
+    /// use tease::{Input, Output, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::default(); 2])
+    ///     .with_outputs(vec![
+    ///         Output::Number { label: Some("Sum".to_string()), precision: 2 },
+    ///         Output::Number { label: Some("Product".to_string()), precision: 4 },
+    ///     ])
+    ///     .with_multi_function(|x: Vec<f64>| (x.iter().sum(), x.iter().product()))
+    ///     .run();
+    /// ```
+    pub fn with_multi_function<G, R>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Fn(Vec<F>) -> R,
+        R: IntoOutputs<F>,
+    {
+        self.function = Box::new(move |x| {
+            let y = predictor(floats(x)?).into_outputs()?;
+            Ok(y.into_iter().map(Value::from_float).collect())
+        });
+        self.background_function = None;
+        self.argument_check = check_floats;
         self
     }
 
-    /// Specify the advanced function to use (note, this will override a function added using `with_function`)
+    /// Specify a function that works with [`Value`]s rather than plain numbers, such as a function
+    /// of text. Like `with_multi_function`, it returns one value for each output.
+    /// ```rust, no_run
+    /// use tease::{Input, Output, Teaser, Value};
+    /// Teaser::<f32>::default()
+    ///     .with_inputs(vec![Input::Text {
+    ///         label: Some("Text".to_string()),
+    ///         initial_value: "Hello, world!".to_string(),
+    ///         multiline: false,
+    ///         placeholder: None,
+    ///     }])
+    ///     .with_output(Output::Text { label: Some("Shouted".to_string()) })
+    ///     .with_value_function(|x: Vec<Value>| vec![Value::Text(x[0].to_string().to_uppercase())])
+    ///     .run();
+    /// ```
+    pub fn with_value_function<G, R>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Fn(Vec<Value>) -> R,
+        R: IntoOutputs<Value>,
+    {
+        self.function = Box::new(move |x| predictor(x).into_outputs());
+        self.background_function = None;
+        self.argument_check = |_| Ok(());
+        self
+    }
+
+    /// Specify a function that takes a tuple of typed arguments, one for each input, and returns
+    /// either a single value or a tuple with one value for each output. When the GUI starts, the
+    /// inputs are checked against the types of the arguments. A returned `Vec` is a single output
+    /// holding a list (like the probabilities shown by an [`Output::Label`]), unlike a `Vec`
+    /// returned by a function added using `with_multi_function`, which has one value for each
+    /// output.
+    /// ```rust, no_run
+    /// use tease::{Input, Output, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![
+    ///         Input::Text {
+    ///             label: Some("Name".to_string()),
+    ///             initial_value: "World".to_string(),
+    ///             multiline: false,
+    ///             placeholder: None,
+    ///         },
+    ///         Input::Number { label: Some("Excitement".to_string()), initial_value: 1.0 },
+    ///     ])
+    ///     .with_output(Output::Text { label: Some("Greeting".to_string()) })
+    ///     .with_typed_function(|(name, excitement): (String, f64)| {
+    ///         format!("Hello, {name}{}", "!".repeat(excitement as usize))
+    ///     })
+    ///     .run();
+    /// ```
+    pub fn with_typed_function<G, A, R>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Fn(A) -> R,
+        A: FromInputs,
+        R: IntoValues,
+    {
+        self.function = Box::new(move |x| predictor(A::from_inputs(x)?).into_values());
+        self.background_function = None;
+        self.argument_check = A::check;
+        self
+    }
+
+    /// Specify a slow function, to be run on a worker thread so that the window stays responsive.
+    /// It takes and returns the same types as a function added using `with_typed_function`, but
+    /// must be `Send` and `Sync` so that the worker can call it. While it runs, the GUI shows how
+    /// long it has been going along with a button to cancel it. Cancelling (or changing an input,
+    /// with `with_live`) throws the result away, but leaves the worker to finish the run and keep
+    /// using the CPU until it does; a function added using `with_streaming_function` can check
+    /// [`Emitter::is_cancelled`] to stop early instead. Models with many inputs of the same type can take them all as
+    /// a `Vec`, e.g. `|x: Vec<f32>| x.iter().sum::<f32>()`.
+    /// ```rust, no_run
+    /// use std::{thread, time::Duration};
+    /// use tease::{Input, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::Number { label: Some("Seconds".to_string()), initial_value: 3.0 }])
+    ///     .with_background_function(|(seconds,): (f64,)| {
+    ///         thread::sleep(Duration::from_secs_f64(seconds));
+    ///         seconds
+    ///     })
+    ///     .run();
+    /// ```
+    pub fn with_background_function<G, A, R>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Send + Sync + Fn(A) -> R,
+        A: FromInputs,
+        R: IntoValues,
+    {
+        let function: BackgroundFunction =
+            Arc::new(move |x, _| predictor(A::from_inputs(x)?).into_values());
+        let shared = function.clone();
+        self.function = Box::new(move |x| shared(x, &Emitter::discard()));
+        self.background_function = Some(function);
+        self.argument_check = A::check;
+        self
+    }
+
+    /// Specify a function that shows its outputs as it goes, like a training loop reporting its
+    /// loss or a model writing text a word at a time. Like a function added using
+    /// `with_background_function` it runs on a worker thread, and it is also handed an [`Emitter`],
+    /// through which it can send the outputs as they stand whenever it likes, and which tells it
+    /// when the run has been cancelled so that it can stop early. What it returns is shown once it
+    /// is done. When served over HTTP, or run on a batch, only what it returns is
+    /// shown.
+    /// ```rust, no_run
+    /// use std::{thread, time::Duration};
+    /// use tease::{Emitter, Input, Output, Teaser};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::Number { label: Some("Steps".to_string()), initial_value: 10.0 }])
+    ///     .with_output(Output::Number { label: Some("Count".to_string()), precision: 0 })
+    ///     .with_streaming_function(|(steps,): (usize,), emitter: &Emitter| {
+    ///         for step in 0..steps {
+    ///             if emitter.is_cancelled() {
+    ///                 break;
+    ///             }
+    ///             emitter.emit(step);
+    ///             thread::sleep(Duration::from_millis(200));
+    ///         }
+    ///         steps
+    ///     })
+    ///     .run();
+    /// ```
+    pub fn with_streaming_function<G, A, R>(mut self, predictor: G) -> Self
+    where
+        G: 'static + Send + Sync + Fn(A, &Emitter) -> R,
+        A: FromInputs,
+        R: IntoValues,
+    {
+        let function: BackgroundFunction =
+            Arc::new(move |x, emitter| predictor(A::from_inputs(x)?, emitter).into_values());
+        let shared = function.clone();
+        self.function = Box::new(move |x| shared(x, &Emitter::discard()));
+        self.background_function = Some(function);
+        self.argument_check = A::check;
+        self
+    }
+
+    /// Specify the advanced function to use (note, this will override a function added using `with_function`).
+    /// The function receives every input paired with its value, which can hold anything the input
+    /// produces (an uploaded image or file, say). Each input is a copy with its `initial_value`
+    /// set to what is currently shown in the GUI, where it has one. The function returns as many
+    /// outputs as it likes, each paired with the value to show in it.
+    /// ```rust, no_run
+    /// use tease::{Input, Output, Teaser, Value};
+    /// Teaser::default()
+    ///     .with_inputs(vec![Input::default(); 2])
+    ///     .with_advanced_function(|inputs: Vec<(Input<f64>, Value)>| {
+    ///         let x: Vec<f64> = inputs
+    ///             .iter()
+    ///             .map(|(_, value)| value.as_float().unwrap_or(0.0))
+    ///             .collect();
+    ///         let sum = Output::Number { label: Some("Sum".to_string()), precision: 2 };
+    ///         let product = Output::Number { label: Some("Product".to_string()), precision: 2 };
+    ///         vec![
+    ///             (sum, Value::Float(x.iter().sum())),
+    ///             (product, Value::Float(x.iter().product())),
+    ///         ]
+    ///     })
+    ///     .run();
+    /// ```
     pub fn with_advanced_function<A>(mut self, predictor: A) -> Self
     where
-        A: 'static + Fn(Vec<Input>) -> Vec<Output>,
+        A: 'static + Fn(Vec<(Input<F>, Value)>) -> Vec<(Output, Value)>,
     {
         self.use_advanced_function = true;
         self.advanced_function = Box::new(predictor);
         self
     }
 
-    /// Run the GUI
-    pub fn run(self)
-    where
-        <F as FromStr>::Err: Debug,
-    {
-        thread_local! {
-            static WEBVIEW: RefCell<HashMap<usize, WebView>> = RefCell::new(HashMap::new());
+    /// Check that every input can be filled in, and that the function can take their values
+    fn validate(&self) -> Result<(), String> {
+        for (idx, input) in self.inputs.iter().enumerate() {
+            if let Input::Matrix {
+                rows,
+                cols,
+                editable_dims: false,
+                ..
+            } = input
+            {
+                if *rows == 0 || *cols == 0 {
+                    return Err(format!(
+                        "{} is a {rows}x{cols} matrix that can't be resized, so it can never be filled in",
+                        input.get_label(idx)
+                    ));
+                }
+            }
+        }
+        if self.use_advanced_function {
+            return Ok(());
         }
+        let kinds: Vec<Kind> = self.inputs.iter().map(Input::kind).collect();
+        (self.argument_check)(&kinds)
+    }
 
-        let mut html = beginning(self.description);
+    /// Assemble the page for the GUI, wired to send inputs back over `transport`
+    fn get_html(&self, transport: Transport) -> String {
+        let batch = self.inputs.iter().all(Input::fits_in_csv);
+        let mut html = beginning(
+            &self.title,
+            self.description.clone(),
+            transport,
+            batch,
+            self.live,
+        );
         for (idx, input) in self.inputs.iter().enumerate() {
             html = format!("{} {}", html, input.get_html(idx));
         }
-        html = format!("{} {} {} {}", html, middle(), self.output.get_html(), end());
-
-        let event_loop = EventLoop::new();
-        let window = WindowBuilder::new()
-            .with_title(self.title)
-            .build(&event_loop)
-            .unwrap();
-
-        let mut webview_settings = WebViewAttributes::default();
-        webview_settings.devtools = true;
-        let mut webview_builder = WebViewBuilder::new(window).unwrap();
-        webview_builder.webview = webview_settings;
-        let _webview = webview_builder
-            .with_html(html)
-            .unwrap()
-            .with_ipc_handler(move |_window: &Window, req: String| {
-                let number_strings = req.split(",");
-                let mut inputs = vec![zero(); 0];
-                for number in number_strings {
-                    inputs.push(number.parse().unwrap());
-                }
+        format!(
+            "{} {} {} {}",
+            html,
+            middle(batch, self.live),
+            self.outputs
+                .iter()
+                .enumerate()
+                .map(|(idx, output)| output.get_html(idx, None).unwrap_or_default())
+                .collect::<String>(),
+            end()
+        )
+    }
 
-                let y = (*self.function)(inputs);
-                WEBVIEW
-                    .with(|webview| {
-                        let webview = webview.borrow();
-                        let my_webview = webview.get(&0).unwrap();
-                        let precision = self.output.get_precision();
-                        my_webview.evaluate_script(&*format!(
-                            "document.getElementById('output').value = {:.precision$}",
-                            y
-                        ))
-                    })
-                    .expect("TODO: panic message");
-            })
-            .build()
-            .unwrap();
-
-        WEBVIEW.with(|wv| {
-            let mut hash = HashMap::new();
-            hash.insert(0_usize, _webview);
-            wv.replace(hash);
-        });
+    /// Write the GUI to a single HTML file, which can be opened in any browser without a server.
+    /// The page runs the function in a WebAssembly module added using
+    /// [`with_wasm_module`](Teaser::with_wasm_module), and without one it only shows the inputs.
+    /// This returns an error if the file can't be written or if the inputs don't match the
+    /// arguments of the function.
+    ///
+    /// To build the module, put the teaser in a library crate of its own with
+    /// `crate-type = ["cdylib", "rlib"]`, depending on `tease` with `default-features = false`, and
+    /// export it with [`export_wasm`].
+    /// ```rust, no_run
+    /// use tease::{export_wasm, Input, Teaser};
+    ///
+    /// pub fn teaser() -> Teaser {
+    ///     Teaser::default()
+    ///         .with_title("Addition".to_string())
+    ///         .with_inputs(vec![Input::default(); 2])
+    ///         .with_function(|x: Vec<f32>| x.iter().sum())
+    /// }
+    ///
+    /// export_wasm!(teaser());
+    /// ```
+    /// Build it with `cargo build --release --target wasm32-unknown-unknown`, and then write the
+    /// page from a small program (or a test) that is compiled for the usual target.
+    /// ```rust, no_run
+    /// # fn teaser() -> tease::Teaser { tease::Teaser::default() }
+    /// let module = std::fs::read("target/wasm32-unknown-unknown/release/addition.wasm").unwrap();
+    /// teaser()
+    ///     .with_wasm_module(module)
+    ///     .export_html("addition.html")
+    ///     .unwrap();
+    /// ```
+    pub fn export_html<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.validate()?;
+        let module = self
+            .wasm_module
+            .as_ref()
+            .map(|module| STANDARD.encode(module));
+        fs::write(path, self.get_html(Transport::Wasm(module)))?;
+        Ok(())
+    }
 
-        event_loop.run(move |event, _, control_flow| {
-            *control_flow = ControlFlow::Wait;
+    /// Answer a JSON [`Request`] from the page with a JSON [`Response`], just as the window and
+    /// the server do. This makes it possible to carry the messages of the page some other way.
+    /// ```rust
+    /// use tease::{Input, Teaser};
+    /// let teaser = Teaser::default()
+    ///     .with_inputs(vec![Input::default(); 2])
+    ///     .with_function(|x: Vec<f32>| x.iter().sum());
+    /// let response = teaser.respond(r#"{"version": 1, "id": 0, "inputs": {"x0": 1, "x1": 2}}"#);
+    /// assert!(response.contains(r#""value":3.0"#));
+    /// ```
+    pub fn respond(&self, message: &str) -> String {
+        let response = match serde_json::from_str::<Request>(message) {
+            Ok(request) => self.handle(request),
+            Err(error) => Response::error(0, format!("Malformed request: {error}")),
+        };
+        serde_json::to_string(&response).expect("Responses can always be serialized")
+    }
 
-            match event {
-                Event::NewEvents(StartCause::Init) => println!("Wry application started!"),
-                Event::WindowEvent {
-                    event: WindowEvent::CloseRequested,
-                    ..
-                } => *control_flow = ControlFlow::ExitWithCode(0),
-                _ => {}
+    /// Run the function on the inputs of a request
+    fn handle(&self, request: Request) -> Response {
+        if request.version != protocol::VERSION {
+            return Response::error(
+                request.id,
+                format!("Unsupported protocol version {}", request.version),
+            );
+        }
+
+        if request.cancel {
+            return Response::error(request.id, "The run was cancelled");
+        }
+
+        if let Some(csv) = &request.batch {
+            return match self.run_batch(csv) {
+                Ok(batch) => Response::batch(request.id, batch),
+                Err(error) => Response::error(request.id, error),
+            };
+        }
+
+        let outputs = self
+            .read_inputs(&request.inputs)
+            .and_then(|inputs| render(&self.compute(inputs)?));
+        match outputs {
+            Ok(outputs) => Response::outputs(request.id, outputs),
+            Err(error) => Response::error(request.id, error),
+        }
+    }
+
+    /// Read the value of each input out of a request, in which they are keyed by name
+    fn read_inputs(
+        &self,
+        values: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<Vec<Value>, String> {
+        let mut inputs = Vec::with_capacity(self.inputs.len());
+        for (idx, input) in self.inputs.iter().enumerate() {
+            match values.get(&format!("x{idx}")) {
+                None => return Err(format!("{} is missing", input.get_label(idx))),
+                Some(value) => inputs.push(input.read(idx, value)?),
             }
-        });
+        }
+        Ok(inputs)
+    }
+
+    /// Run the function on the values of the inputs, pairing each result with the output that
+    /// shows it
+    fn compute(&self, inputs: Vec<Value>) -> Result<Vec<(Output, Value)>, String> {
+        if self.use_advanced_function {
+            let inputs = self
+                .inputs
+                .iter()
+                .zip(inputs)
+                .map(|(input, x)| (input.with_value(&x), x))
+                .collect();
+            catch_panic(|| Ok((*self.advanced_function)(inputs)))
+        } else {
+            call(&*self.function, &self.outputs, inputs)
+        }
+    }
+}
+
+/// Read a number out of a JSON value, accepting numeric strings as well as numbers
+fn read_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(string) => string.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Read the selected option of a dropdown or radio buttons out of a JSON value, which can hold
+/// either the text of the option or its index
+fn read_choice<F>(options: &[(String, F)], value: &serde_json::Value) -> Option<usize> {
+    match value {
+        serde_json::Value::String(text) => {
+            if let Some(idx) = options.iter().position(|(option, _)| option == text) {
+                return Some(idx);
+            }
+        }
+        serde_json::Value::Number(_) => {}
+        _ => return None,
+    }
+    read_number(value)
+        .filter(|idx| idx.fract() == 0.0 && *idx >= 0.0 && (*idx as usize) < options.len())
+        .map(|idx| idx as usize)
+}
+
+/// Read an uploaded file out of a JSON value holding its `name`, `mime` type and base64-encoded
+/// `bytes`
+fn read_upload(value: &serde_json::Value) -> Option<Upload> {
+    let text = |key: &str| value.get(key)?.as_str();
+    Some(Upload {
+        name: text("name")?.to_string(),
+        mime: text("mime").unwrap_or_default().to_string(),
+        bytes: STANDARD.decode(text("bytes")?).ok()?,
+    })
+}
+
+/// The fastest sample rate accepted from the page, which is well beyond any audio device
+const MAX_SAMPLE_RATE: u64 = 768_000;
+
+/// Read a recording out of a JSON value holding its `sample_rate` (between 1 and
+/// [`MAX_SAMPLE_RATE`]) and its `samples`, as base64-encoded little-endian 32-bit floats
+fn read_audio(value: &serde_json::Value) -> Option<Audio> {
+    let bytes = STANDARD.decode(value.get("samples")?.as_str()?).ok()?;
+    if bytes.len() % 4 != 0 {
+        return None;
+    }
+    Some(Audio {
+        samples: bytes
+            .chunks_exact(4)
+            .map(|x| f32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+            .collect(),
+        sample_rate: value
+            .get("sample_rate")?
+            .as_u64()
+            .filter(|rate| (1..=MAX_SAMPLE_RATE).contains(rate))?
+            .try_into()
+            .ok()?,
+    })
+}
+
+/// Read a checkbox out of a JSON value, accepting 1 and 0 as well as `true` and `false`
+fn read_bool(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(check) => Some(*check),
+        value => read_number(value).map(|x| x != 0.0),
+    }
+}
+
+/// Check that every input produces numbers, for a function that only works with numbers
+fn check_floats(kinds: &[Kind]) -> Result<(), String> {
+    match kinds
+        .iter()
+        .enumerate()
+        .find(|(_, kind)| matches!(kind, Kind::Text | Kind::Bytes | Kind::File | Kind::Audio))
+    {
+        Some((idx, kind)) => Err(format!(
+            "Input {idx} produces {kind}, so the function must be added using `with_value_function` or `with_typed_function`"
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Unwrap the numbers passed to a function that only works with numbers. Inputs that produce a list
+/// of values (like a group of checkboxes, or the rows of a matrix) are spread out into a number for
+/// each value.
+fn floats<F: Float>(values: Vec<Value>) -> Result<Vec<F>, String> {
+    /// Push the numbers held by `value` onto `floats`, or give up if it holds anything else
+    fn flatten<F: Float>(value: &Value, floats: &mut Vec<F>) -> Option<()> {
+        match value {
+            Value::List(items) => items.iter().try_for_each(|item| flatten(item, floats)),
+            value => {
+                floats.push(value.to_float()?);
+                Some(())
+            }
+        }
+    }
+
+    let mut floats = Vec::with_capacity(values.len());
+    for (idx, value) in values.iter().enumerate() {
+        flatten(value, &mut floats).ok_or_else(|| {
+            format!("Input {idx} is not a number, so the function must be added using `with_value_function`")
+        })?;
+    }
+    Ok(floats)
+}
+
+/// Run `function` on the values of the inputs, pairing each result with the output that shows it
+fn call(
+    function: &dyn Fn(Vec<Value>) -> Result<Vec<Value>, String>,
+    outputs: &[Output],
+    inputs: Vec<Value>,
+) -> Result<Vec<(Output, Value)>, String> {
+    pair(outputs, catch_panic(|| function(inputs))?)
+}
+
+/// Pair each value returned by the function with the output that shows it
+fn pair(outputs: &[Output], y: Vec<Value>) -> Result<Vec<(Output, Value)>, String> {
+    if y.len() != outputs.len() {
+        return Err(format!(
+            "The function returned {} values, but there are {} outputs",
+            y.len(),
+            outputs.len()
+        ));
+    }
+    Ok(outputs.iter().cloned().zip(y).collect())
+}
+
+/// Package the results of the function for the page, turning a panic into an error
+fn render(results: &[(Output, Value)]) -> Result<Vec<OutputValue>, String> {
+    catch_panic(|| {
+        results
+            .iter()
+            .enumerate()
+            .map(|(idx, (output, y))| output.render(idx, y))
+            .collect()
+    })
+}
+
+/// Run `f`, turning a panic into an error
+fn catch_panic<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(panic) => Err(format!("The function panicked: {}", panic_message(&*panic))),
+    }
+}
+
+/// Recover the message from the payload of a caught panic
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "no message"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::json;
+
+    use super::{read_audio, read_choice, Input, Output, Teaser, Value, MAX_SAMPLE_RATE};
+
+    /// Three options, each paired with a value unlike its index
+    fn options() -> Vec<(String, f64)> {
+        ["Red", "Green", "Blue"]
+            .iter()
+            .zip([10.0, 20.0, 30.0])
+            .map(|(text, x)| (text.to_string(), x))
+            .collect()
+    }
+
+    /// A dropdown of [`options`], starting out on the first
+    fn dropdown(one_hot: bool) -> Input<f64> {
+        Input::Dropdown {
+            label: Some("Color".to_string()),
+            options: options(),
+            initial_value: 0,
+            one_hot,
+        }
+    }
+
+    /// A recording of two samples at `sample_rate`, as the page sends it
+    fn recording(sample_rate: u64) -> serde_json::Value {
+        let samples: Vec<u8> = [0.5f32, -0.5]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        json!({ "sample_rate": sample_rate, "samples": STANDARD.encode(samples) })
+    }
+
+    #[test]
+    fn recordings_are_read() {
+        let audio = read_audio(&recording(44_100)).unwrap();
+        assert_eq!(audio.sample_rate, 44_100);
+        assert_eq!(audio.samples, [0.5, -0.5]);
+    }
+
+    #[test]
+    fn sample_rates_must_be_sane() {
+        assert!(read_audio(&recording(0)).is_none());
+        assert!(read_audio(&recording(MAX_SAMPLE_RATE)).is_some());
+        assert!(read_audio(&recording(MAX_SAMPLE_RATE + 1)).is_none());
+    }
+
+    #[test]
+    fn choices_are_read_by_label_or_index() {
+        assert_eq!(read_choice(&options(), &json!("Green")), Some(1));
+        assert_eq!(read_choice(&options(), &json!(2)), Some(2));
+        assert_eq!(read_choice(&options(), &json!("2")), Some(2));
+        assert_eq!(read_choice(&options(), &json!("Purple")), None);
+        assert_eq!(read_choice(&options(), &json!(true)), None);
+    }
+
+    #[test]
+    fn choices_out_of_range_are_rejected() {
+        assert_eq!(read_choice(&options(), &json!(3)), None);
+        assert_eq!(read_choice(&options(), &json!(-1)), None);
+        assert_eq!(read_choice(&options(), &json!(1.5)), None);
+    }
+
+    #[test]
+    fn dropdowns_give_the_value_of_the_choice() {
+        assert_eq!(
+            dropdown(false).read(0, &json!("Blue")),
+            Ok(Value::Float(30.0))
+        );
+        assert_eq!(
+            dropdown(false).read(0, &json!(5)),
+            Err("Color must be one of the options, not 5".to_string())
+        );
+    }
+
+    #[test]
+    fn one_hot_dropdowns_give_a_one_for_the_choice() {
+        let hot = |x: f64| Value::Float(x);
+        assert_eq!(
+            dropdown(true).read(0, &json!("Green")),
+            Ok(Value::List(vec![hot(0.0), hot(1.0), hot(0.0)]))
+        );
+        assert_eq!(
+            dropdown(true).read(0, &json!(0)),
+            Ok(Value::List(vec![hot(1.0), hot(0.0), hot(0.0)]))
+        );
+    }
+
+    #[test]
+    fn fixed_matrices_need_rows_and_columns() {
+        let matrix = |rows, cols, editable_dims| Input::<f32>::Matrix {
+            label: Some("Weights".to_string()),
+            rows,
+            cols,
+            initial_values: vec![],
+            editable_dims,
+        };
+        let teaser = |input| {
+            Teaser::default()
+                .with_inputs(vec![input])
+                .with_typed_function(|grid: Vec<Vec<f64>>| grid.len() as f64)
+        };
+        assert_eq!(
+            teaser(matrix(0, 3, false)).validate(),
+            Err(
+                "Weights is a 0x3 matrix that can't be resized, so it can never be filled in"
+                    .to_string()
+            )
+        );
+        assert!(teaser(matrix(2, 0, false)).validate().is_err());
+        assert_eq!(teaser(matrix(0, 0, true)).validate(), Ok(()));
+        assert_eq!(teaser(matrix(2, 3, false)).validate(), Ok(()));
+    }
+
+    #[test]
+    fn the_top_class_is_predicted_wherever_it_comes() {
+        let output = |top_k| Output::Label {
+            label: None,
+            class_names: vec!["Dog".to_string(), "Bird".to_string(), "Cat".to_string()],
+            top_k,
+        };
+        let probabilities =
+            Value::List(vec![0.1, 0.2, 0.7].into_iter().map(Value::Float).collect());
+        assert_eq!(
+            output(1).rank_classes(0, &probabilities),
+            Ok(("Cat".to_string(), vec![("Cat".to_string(), 0.7)]))
+        );
+        assert_eq!(
+            output(0).rank_classes(0, &probabilities),
+            Ok(("Cat".to_string(), vec![]))
+        );
     }
 }
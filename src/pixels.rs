@@ -0,0 +1,374 @@
+//! Turning the pixels drawn by the page into the buffers handed to the function, and the images
+//! returned by the function into PNG files that the page can show
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::Value;
+
+/// The channels kept from each pixel of an image
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColorMode {
+    /// A single brightness channel
+    Grayscale,
+    /// Red, green and blue channels
+    Rgb,
+    /// Red, green, blue and alpha channels
+    Rgba,
+}
+
+impl ColorMode {
+    /// The number of channels in each pixel
+    pub fn channels(&self) -> usize {
+        match self {
+            ColorMode::Grayscale => 1,
+            ColorMode::Rgb => 3,
+            ColorMode::Rgba => 4,
+        }
+    }
+
+    /// The name of the color mode, as it is sent to the page
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ColorMode::Grayscale => "grayscale",
+            ColorMode::Rgb => "rgb",
+            ColorMode::Rgba => "rgba",
+        }
+    }
+
+    /// The PNG color type for pixels with these channels
+    fn png_color_type(&self) -> u8 {
+        match self {
+            ColorMode::Grayscale => 0,
+            ColorMode::Rgb => 2,
+            ColorMode::Rgba => 6,
+        }
+    }
+}
+
+/// The order in which the values of an image are laid out in the buffer handed to the function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PixelLayout {
+    /// Row by row, with the channels of each pixel next to each other (height, width, channel)
+    Hwc,
+    /// Channel by channel, each of which is laid out row by row (channel, height, width)
+    Chw,
+}
+
+/// Decode the base64-encoded RGBA pixels of a `width` by `height` canvas into a buffer of values
+/// between 0 and 1, keeping the channels of `color_mode` in the order given by `layout`
+pub(crate) fn decode(
+    encoded: &str,
+    width: usize,
+    height: usize,
+    color_mode: ColorMode,
+    layout: PixelLayout,
+) -> Result<Value, String> {
+    let rgba = STANDARD
+        .decode(encoded)
+        .map_err(|error| error.to_string())?;
+    if rgba.len() != width * height * 4 {
+        return Err(format!(
+            "expected {width}x{height} pixels, but got {} bytes",
+            rgba.len()
+        ));
+    }
+
+    let channels = color_mode.channels();
+    let mut buffer = vec![Value::Float(0.0); width * height * channels];
+    for (pixel, rgba) in rgba.chunks_exact(4).enumerate() {
+        let [r, g, b, a] = [rgba[0], rgba[1], rgba[2], rgba[3]].map(f64::from);
+        let values = match color_mode {
+            ColorMode::Grayscale => vec![(299.0 * r + 587.0 * g + 114.0 * b) / 255_000.0],
+            ColorMode::Rgb => vec![r / 255.0, g / 255.0, b / 255.0],
+            ColorMode::Rgba => vec![r / 255.0, g / 255.0, b / 255.0, a / 255.0],
+        };
+        for (channel, value) in values.into_iter().enumerate() {
+            let idx = match layout {
+                PixelLayout::Hwc => pixel * channels + channel,
+                PixelLayout::Chw => channel * width * height + pixel,
+            };
+            buffer[idx] = Value::Float(value);
+        }
+    }
+    Ok(Value::List(buffer))
+}
+
+/// An image returned by the function, as 8-bit pixels laid out row by row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    /// Width of the image, in pixels
+    pub width: usize,
+    /// Height of the image, in pixels
+    pub height: usize,
+    /// Channels of each pixel
+    pub color_mode: ColorMode,
+    /// The channels of each pixel, pixel by pixel and row by row (height, width, channel)
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Build an image out of values between 0 and 1, laid out pixel by pixel and row by row
+    /// (height, width, channel), like a saliency map or a segmentation mask
+    pub fn from_floats(width: usize, height: usize, color_mode: ColorMode, values: &[f32]) -> Self {
+        Self {
+            width,
+            height,
+            color_mode,
+            pixels: values
+                .iter()
+                .map(|x| (x.clamp(0.0, 1.0) * 255.0).round() as u8)
+                .collect(),
+        }
+    }
+
+    /// Encode the image as a PNG file, or explain why it can't be
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format!(
+                "a {}x{} image, which has no pixels",
+                self.width, self.height
+            ));
+        }
+        let row_size = self.width * self.color_mode.channels();
+        if self.pixels.len() != row_size * self.height {
+            return Err(format!(
+                "a {}x{} {} image, which needs {} values, not {}",
+                self.width,
+                self.height,
+                self.color_mode.name(),
+                row_size * self.height,
+                self.pixels.len()
+            ));
+        }
+        let (width, height) = match (u32::try_from(self.width), u32::try_from(self.height)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(format!("{}x{} is too large", self.width, self.height)),
+        };
+
+        // Each row starts with a byte choosing how it is filtered, which is 0 for no filter
+        let mut scanlines = Vec::with_capacity((row_size + 1) * self.height);
+        for row in self.pixels.chunks(row_size).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        }
+
+        let mut header = vec![];
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[8, self.color_mode.png_color_type(), 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        write_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+/// Append a PNG chunk, with its length and checksum, to `png`
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream without compressing it, which every PNG decoder can read
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        stream.push(u8::from(blocks.peek().is_none()));
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    stream.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    stream
+}
+
+/// The CRC-32 checksum used by PNG chunks
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    use super::{crc32, decode, zlib_stored, ColorMode, Image, PixelLayout};
+    use crate::Value;
+
+    /// A 2x1 canvas holding a red pixel and a half-transparent teal one, as the page sends it
+    const CANVAS: [u8; 8] = [255, 0, 0, 255, 0, 255, 51, 128];
+
+    /// Decode [`CANVAS`] into a list of numbers
+    fn decode_canvas(color_mode: ColorMode, layout: PixelLayout) -> Vec<f64> {
+        match decode(&STANDARD.encode(CANVAS), 2, 1, color_mode, layout).unwrap() {
+            Value::List(values) => values.iter().map(|x| x.as_float().unwrap()).collect(),
+            value => panic!("{value:?} is not a list"),
+        }
+    }
+
+    /// The Adler-32 checksum at the end of a zlib stream
+    fn adler32(stream: &[u8]) -> u32 {
+        let tail = &stream[stream.len() - 4..];
+        u32::from_be_bytes([tail[0], tail[1], tail[2], tail[3]])
+    }
+
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn adler32_matches_the_check_value() {
+        assert_eq!(adler32(&zlib_stored(b"Wikipedia")), 0x11e6_0398);
+        assert_eq!(adler32(&zlib_stored(b"")), 1);
+    }
+
+    #[test]
+    fn empty_data_is_one_final_block() {
+        assert_eq!(
+            zlib_stored(b""),
+            [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn long_data_is_split_into_stored_blocks() {
+        let data: Vec<u8> = (0..150_000u32).map(|x| (x % 251) as u8).collect();
+        let stream = zlib_stored(&data);
+        assert_eq!(&stream[..2], [0x78, 0x01]);
+
+        // Walk the blocks, checking each header and gathering the data back up
+        let (mut pos, mut unpacked, mut lens) = (2, vec![], vec![]);
+        loop {
+            let last = stream[pos];
+            let len = u16::from_le_bytes([stream[pos + 1], stream[pos + 2]]);
+            let nlen = u16::from_le_bytes([stream[pos + 3], stream[pos + 4]]);
+            assert_eq!(nlen, !len);
+            unpacked.extend_from_slice(&stream[pos + 5..pos + 5 + len as usize]);
+            lens.push(len);
+            pos += 5 + len as usize;
+            if last == 1 {
+                break;
+            }
+            assert_eq!(last, 0);
+        }
+        assert_eq!(lens, [65535, 65535, 18930]);
+        assert_eq!(unpacked, data);
+        assert_eq!(pos + 4, stream.len());
+    }
+
+    #[test]
+    fn mismatched_pixel_counts_are_rejected() {
+        let image = Image {
+            width: 2,
+            height: 2,
+            color_mode: ColorMode::Rgb,
+            pixels: vec![0; 11],
+        };
+        assert_eq!(
+            image.to_png(),
+            Err("a 2x2 rgb image, which needs 12 values, not 11".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_images_are_rejected() {
+        for (width, height) in [(0, 2), (2, 0), (0, 0)] {
+            let image = Image {
+                width,
+                height,
+                color_mode: ColorMode::Rgb,
+                pixels: vec![],
+            };
+            assert_eq!(
+                image.to_png(),
+                Err(format!("a {width}x{height} image, which has no pixels"))
+            );
+        }
+    }
+
+    #[test]
+    fn pngs_are_framed_in_checksummed_chunks() {
+        let png = Image::from_floats(1, 1, ColorMode::Grayscale, &[1.0])
+            .to_png()
+            .unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        // The checksum of the header covers its type and its 13 bytes of data
+        let crc = u32::from_be_bytes([png[29], png[30], png[31], png[32]]);
+        assert_eq!(crc, crc32(&png[12..29]));
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn channels_are_laid_out_last_or_first() {
+        let hwc = decode_canvas(ColorMode::Rgb, PixelLayout::Hwc);
+        let chw = decode_canvas(ColorMode::Rgb, PixelLayout::Chw);
+        assert_eq!(hwc, [1.0, 0.0, 0.0, 0.0, 1.0, 0.2]);
+        assert_eq!(chw, [1.0, 0.0, 0.0, 1.0, 0.0, 0.2]);
+        for pixel in 0..2 {
+            for channel in 0..3 {
+                assert_eq!(hwc[pixel * 3 + channel], chw[channel * 2 + pixel]);
+            }
+        }
+    }
+
+    #[test]
+    fn alpha_is_kept_in_rgba() {
+        let rgba = decode_canvas(ColorMode::Rgba, PixelLayout::Hwc);
+        assert_eq!(rgba[3], 1.0);
+        assert_eq!(rgba[7], 128.0 / 255.0);
+    }
+
+    #[test]
+    fn grayscale_weighs_the_channels_by_brightness() {
+        let gray = decode_canvas(ColorMode::Grayscale, PixelLayout::Chw);
+        assert_eq!(gray.len(), 2);
+        assert!((gray[0] - 0.299).abs() < 1e-12);
+        assert!((gray[1] - (0.587 + 0.114 * 0.2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn canvases_of_the_wrong_size_are_rejected() {
+        assert_eq!(
+            decode(
+                &STANDARD.encode(CANVAS),
+                3,
+                1,
+                ColorMode::Rgb,
+                PixelLayout::Hwc
+            ),
+            Err("expected 3x1 pixels, but got 8 bytes".to_string())
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Drawing plots as SVG, so that they show up in the page without any charting library
+
+use crate::{html_chunks::escape, Value};
+
+/// How the points of a plot are drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlotKind {
+    /// Points joined by lines, in order
+    Line,
+    /// Separate points
+    Scatter,
+    /// A bar rising from zero to each point
+    Bar,
+}
+
+/// Width of the drawing, in SVG units
+const WIDTH: f64 = 480.0;
+/// Height of the drawing, in SVG units
+const HEIGHT: f64 = 320.0;
+/// Space left around the axes for ticks and labels, as (left, right, top, bottom)
+const MARGIN: (f64, f64, f64, f64) = (60.0, 16.0, 12.0, 48.0);
+/// Colors given to the series, in order
+const COLORS: [&str; 6] = [
+    "#007bff", "#dc3545", "#28a745", "#fd7e14", "#6f42c1", "#17a2b8",
+];
+
+/// Read the series of points held by a value. A list of numbers is a single series whose points
+/// are spaced one apart, a list of `(x, y)` pairs is a single series, and a list of either of
+/// those is several series.
+pub(crate) fn read_series(value: &Value) -> Option<Vec<Vec<(f64, f64)>>> {
+    let items = value.as_list()?;
+    if let Some(series) = read_points(items) {
+        return Some(vec![series]);
+    }
+    items
+        .iter()
+        .map(|series| read_points(series.as_list()?))
+        .collect()
+}
+
+/// Read a single series of points, given either as numbers or as `(x, y)` pairs
+fn read_points(items: &[Value]) -> Option<Vec<(f64, f64)>> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| match item {
+            Value::List(pair) if pair.len() == 2 => {
+                Some((pair[0].as_float()?, pair[1].as_float()?))
+            }
+            item => Some((idx as f64, item.as_float()?)),
+        })
+        .collect()
+}
+
+/// Draw the series as an SVG plot
+pub(crate) fn render_svg(
+    kind: PlotKind,
+    series: &[Vec<(f64, f64)>],
+    x_label: &Option<String>,
+    y_label: &Option<String>,
+) -> String {
+    let points = series
+        .iter()
+        .flatten()
+        .filter(|(x, y)| x.is_finite() && y.is_finite());
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = points.fold(
+        (
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ),
+        |(x_min, x_max, y_min, y_max), (x, y)| {
+            (x_min.min(*x), x_max.max(*x), y_min.min(*y), y_max.max(*y))
+        },
+    );
+    if x_min > x_max {
+        (x_min, x_max, y_min, y_max) = (0.0, 1.0, 0.0, 1.0);
+    }
+
+    // Bars rise from zero, and need room on either side of the first and last ones
+    let bar_width = match kind {
+        PlotKind::Bar => {
+            y_min = y_min.min(0.0);
+            y_max = y_max.max(0.0);
+            let mut xs = series.iter().flatten().map(|(x, _)| *x).collect::<Vec<_>>();
+            xs.sort_by(f64::total_cmp);
+            let spacing = xs
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .filter(|gap| *gap > 0.0)
+                .fold(f64::INFINITY, f64::min);
+            let spacing = if spacing.is_finite() { spacing } else { 1.0 };
+            x_min -= spacing / 2.0;
+            x_max += spacing / 2.0;
+            0.8 * spacing / series.len().max(1) as f64
+        }
+        _ => 0.0,
+    };
+    let (x_ticks, x_min, x_max) = ticks(x_min, x_max);
+    let (y_ticks, y_min, y_max) = ticks(y_min, y_max);
+
+    let (left, right, top, bottom) = MARGIN;
+    let scale_x = |x: f64| left + (x - x_min) / (x_max - x_min) * (WIDTH - left - right);
+    let scale_y =
+        |y: f64| HEIGHT - bottom - (y - y_min) / (y_max - y_min) * (HEIGHT - top - bottom);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {WIDTH} {HEIGHT}\" class=\"w-100\" font-size=\"12\">"
+    );
+
+    // Grid lines and ticks
+    for (x, text) in &x_ticks {
+        let sx = scale_x(*x);
+        svg += &format!(
+            "<line x1=\"{sx:.1}\" x2=\"{sx:.1}\" y1=\"{top}\" y2=\"{:.1}\" stroke=\"#e9ecef\"/>\
+             <text x=\"{sx:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{text}</text>",
+            HEIGHT - bottom,
+            HEIGHT - bottom + 16.0
+        );
+    }
+    for (y, text) in &y_ticks {
+        let sy = scale_y(*y);
+        svg += &format!(
+            "<line x1=\"{left}\" x2=\"{:.1}\" y1=\"{sy:.1}\" y2=\"{sy:.1}\" stroke=\"#e9ecef\"/>\
+             <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{text}</text>",
+            WIDTH - right,
+            left - 6.0,
+            sy + 4.0
+        );
+    }
+    svg += &format!(
+        "<rect x=\"{left}\" y=\"{top}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#6c757d\"/>",
+        WIDTH - left - right,
+        HEIGHT - top - bottom
+    );
+
+    // Axis labels
+    if let Some(x_label) = x_label {
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            (left + WIDTH - right) / 2.0,
+            HEIGHT - 8.0,
+            escape(x_label)
+        );
+    }
+    if let Some(y_label) = y_label {
+        let (x, y) = (14.0, (top + HEIGHT - bottom) / 2.0);
+        svg += &format!(
+            "<text x=\"{x}\" y=\"{y:.1}\" text-anchor=\"middle\" transform=\"rotate(-90 {x} {y:.1})\">{}</text>",
+            escape(y_label)
+        );
+    }
+
+    // The points themselves
+    for (idx, points) in series.iter().enumerate() {
+        let color = COLORS[idx % COLORS.len()];
+        let points = points
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite());
+        match kind {
+            PlotKind::Line => {
+                let path = points
+                    .map(|(x, y)| format!("{:.1},{:.1}", scale_x(*x), scale_y(*y)))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                svg += &format!(
+                    "<polyline points=\"{path}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>"
+                );
+            }
+            PlotKind::Scatter => {
+                for (x, y) in points {
+                    svg += &format!(
+                        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{color}\"/>",
+                        scale_x(*x),
+                        scale_y(*y)
+                    );
+                }
+            }
+            PlotKind::Bar => {
+                for (x, y) in points {
+                    let offset = (idx as f64 - (series.len() - 1) as f64 / 2.0) * bar_width;
+                    let (x0, x1) = (
+                        scale_x(x + offset - bar_width / 2.0),
+                        scale_x(x + offset + bar_width / 2.0),
+                    );
+                    let (y0, y1) = (scale_y(y.max(0.0)), scale_y(y.min(0.0)));
+                    svg += &format!(
+                        "<rect x=\"{x0:.1}\" y=\"{y0:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{color}\"/>",
+                        x1 - x0,
+                        y1 - y0
+                    );
+                }
+            }
+        }
+    }
+
+    svg + "</svg>"
+}
+
+/// Round numbers between `min` and `max` to put ticks at, along with the range of the axis
+fn ticks(min: f64, max: f64) -> (Vec<(f64, String)>, f64, f64) {
+    // A constant series gets a range around it, wide enough to survive rounding
+    let (min, max) = if max - min > f64::EPSILON * max.abs().max(1.0) {
+        (min, max)
+    } else {
+        let pad = (0.1 * max.abs()).max(0.5);
+        (min - pad, max + pad)
+    };
+
+    // Pick a step of 1, 2 or 5 times a power of ten, giving about five ticks. The range is
+    // divided before subtracting so that it can't overflow.
+    let rough = max / 5.0 - min / 5.0;
+    let power = rough.log10().floor();
+    let magnitude = 10f64.powf(power);
+    let (multiple, step): (i64, f64) = [1, 2, 5, 10]
+        .iter()
+        .map(|m| (*m, *m as f64 * magnitude))
+        .find(|(_, step)| *step >= rough)
+        .unwrap_or((10, 10.0 * magnitude));
+
+    // Very large and very small numbers are written in scientific notation, to keep labels short
+    let scientific = step < 1e-4 || min.abs().max(max.abs()) >= 1e6;
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    let ticks = (first..=last)
+        .map(|idx| {
+            let x = idx as f64 * step;
+            let label = if scientific {
+                scientific_label(idx * multiple, power as i32)
+            } else {
+                format!("{x:.decimals$}")
+            };
+            (x, label)
+        })
+        .collect();
+    (ticks, min, max)
+}
+
+/// Write `digits` times ten to the `power` in scientific notation, e.g. `1.5e7`
+fn scientific_label(mut digits: i64, mut power: i32) -> String {
+    if digits == 0 {
+        return "0".to_string();
+    }
+    while digits % 10 == 0 {
+        digits /= 10;
+        power += 1;
+    }
+    let sign = if digits < 0 { "-" } else { "" };
+    let text = digits.unsigned_abs().to_string();
+    let exponent = power + text.len() as i32 - 1;
+    match text.split_at(1) {
+        (lead, "") => format!("{sign}{lead}e{exponent}"),
+        (lead, rest) => format!("{sign}{lead}.{rest}e{exponent}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_series, render_svg, ticks, PlotKind, MARGIN, WIDTH};
+    use crate::Value;
+
+    /// A list of numbers
+    fn list(xs: &[f64]) -> Value {
+        Value::List(xs.iter().map(|x| Value::Float(*x)).collect())
+    }
+
+    /// The labels of the ticks between `min` and `max`
+    fn labels(min: f64, max: f64) -> Vec<String> {
+        ticks(min, max)
+            .0
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    /// The x position and width of each bar in a plot, in SVG units
+    fn bars(svg: &str) -> Vec<(f64, f64)> {
+        svg.split("<rect ")
+            .skip(1)
+            .filter(|rect| !rect.contains("fill=\"none\""))
+            .map(|rect| {
+                let attribute = |name: &str| -> f64 {
+                    let start = rect.find(&format!("{name}=\"")).unwrap() + name.len() + 2;
+                    let end = start + rect[start..].find('"').unwrap();
+                    rect[start..end].parse().unwrap()
+                };
+                (attribute("x"), attribute("width"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ticks_fall_on_round_numbers() {
+        assert_eq!(labels(0.0, 10.0), ["0", "2", "4", "6", "8", "10"]);
+        assert_eq!(labels(-2.5, 7.3), ["-2", "0", "2", "4", "6"]);
+        assert_eq!(ticks(0.0, 10.0).1, 0.0);
+        assert_eq!(ticks(0.0, 10.0).2, 10.0);
+    }
+
+    #[test]
+    fn constant_series_get_a_range() {
+        let (_, min, max) = ticks(3.0, 3.0);
+        assert_eq!((min, max), (2.5, 3.5));
+        assert_eq!(labels(3.0, 3.0), ["2.6", "2.8", "3.0", "3.2", "3.4"]);
+        assert_eq!(labels(0.0, 0.0), ["-0.4", "-0.2", "0.0", "0.2", "0.4"]);
+        assert_eq!(
+            labels(1e20, 1e20),
+            ["9e19", "9.5e19", "1e20", "1.05e20", "1.1e20"]
+        );
+    }
+
+    #[test]
+    fn extreme_magnitudes_are_written_in_scientific_notation() {
+        assert_eq!(
+            labels(1e-12, 5e-12),
+            ["1e-12", "2e-12", "3e-12", "4e-12", "5e-12"]
+        );
+        assert_eq!(
+            labels(-5e6, 0.0),
+            ["-5e6", "-4e6", "-3e6", "-2e6", "-1e6", "0"]
+        );
+        assert_eq!(
+            labels(-1e308, 1e308),
+            ["-1e308", "-5e307", "0", "5e307", "1e308"]
+        );
+        let (ticks, _, _) = ticks(1.0, f64::MAX);
+        assert!(ticks.iter().all(|(x, _)| x.is_finite()));
+    }
+
+    #[test]
+    fn bare_lists_are_one_series() {
+        assert_eq!(
+            read_series(&list(&[3.0, 1.0])),
+            Some(vec![vec![(0.0, 3.0), (1.0, 1.0)]])
+        );
+        let pairs = Value::List(vec![list(&[0.5, 3.0]), list(&[1.5, 1.0])]);
+        assert_eq!(
+            read_series(&pairs),
+            Some(vec![vec![(0.5, 3.0), (1.5, 1.0)]])
+        );
+    }
+
+    #[test]
+    fn lists_of_lists_are_several_series() {
+        let series = Value::List(vec![list(&[1.0, 2.0, 3.0]), list(&[4.0])]);
+        assert_eq!(
+            read_series(&series),
+            Some(vec![
+                vec![(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)],
+                vec![(0.0, 4.0)]
+            ])
+        );
+    }
+
+    #[test]
+    fn ragged_points_are_rejected() {
+        let ragged = Value::List(vec![Value::List(vec![
+            list(&[0.0, 1.0]),
+            list(&[1.0, 2.0, 3.0]),
+        ])]);
+        assert_eq!(read_series(&ragged), None);
+        let text = Value::List(vec![Value::Float(1.0), Value::Text("two".to_string())]);
+        assert_eq!(read_series(&text), None);
+        assert_eq!(read_series(&Value::Float(1.0)), None);
+    }
+
+    #[test]
+    fn bars_share_the_spacing_of_the_points() {
+        // Three bars in one series and two in the other, one unit apart
+        let series = vec![
+            vec![(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)],
+            vec![(0.0, 2.0), (1.0, 1.0)],
+        ];
+        let bars = bars(&render_svg(PlotKind::Bar, &series, &None, &None));
+        assert_eq!(bars.len(), 5);
+
+        // The axis runs from half a unit before the first bar to half a unit after the last
+        let (left, right, _, _) = MARGIN;
+        let unit = (WIDTH - left - right) / 3.0;
+        for (_, width) in &bars {
+            assert!((width - 0.4 * unit).abs() < 0.2);
+        }
+        // Bars at the same x sit side by side instead of on top of each other
+        let (first, second) = (bars[0], bars[3]);
+        assert!((second.0 - (first.0 + first.1)).abs() < 0.2);
+    }
+}
@@ -0,0 +1,154 @@
+//! # The messages passed between a teaser's page and its function
+//! Every transport (the wry window, the HTTP server and exported pages alike) speaks the same JSON
+//! protocol. The page sends a [`Request`] holding the current value of each input, keyed by the
+//! input's name, and gets back a [`Response`] with either the outputs of the function or an error.
+//! ```json
+//! {"version": 1, "id": 7, "inputs": {"x0": 1.5, "x1": 2.0}}
+//! {"version": 1, "id": 7, "outputs": [{"value": 3.5, "display": "<label ...>...</label> <input ... value=\"3.50\" readonly>"}]}
+//! {"version": 1, "id": 8, "error": "Input 1 must be a number, not \"abc\""}
+//! ```
+//! A request can instead carry a CSV file in its `batch` field, with a column for each input
+//! named after the input's label. The function is then run on every row, and the response holds
+//! the file with a column added for each output.
+//! ```json
+//! {"version": 1, "id": 9, "inputs": {}, "batch": "Input 0,Input 1\n1,2\n3,4\n"}
+//! {"version": 1, "id": 9, "batch": {"csv": "Input 0,Input 1,Result\n1,2,3.00\n3,4,7.00\n", "display": "..."}}
+//! ```
+//! A function that streams its outputs answers the same request several times, with `partial`
+//! outputs for as long as it is running and the usual `outputs` once it is done.
+//! ```json
+//! {"version": 1, "id": 10, "partial": [{"value": 1.0, "display": "..."}]}
+//! {"version": 1, "id": 10, "partial": [{"value": 2.0, "display": "..."}]}
+//! {"version": 1, "id": 10, "outputs": [{"value": 3.0, "display": "..."}]}
+//! ```
+//! Cancelling the request stops the page waiting for it, and asks the function to stop early.
+//! ```json
+//! {"version": 1, "id": 10, "cancel": true}
+//! {"version": 1, "id": 10, "error": "The run was cancelled"}
+//! ```
+//! Outputs emitted along the way that can't be shown are reported as a `partial_error`, which
+//! leaves the function running.
+//! ```json
+//! {"version": 1, "id": 11, "partial_error": "The function returned 2 values, but there are 3 outputs"}
+//! ```
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The version of the protocol spoken by this crate. Requests for any other version are rejected.
+pub const VERSION: u32 = 1;
+
+/// A request from the page to run the function
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Request {
+    /// Version of the protocol that the request was written for
+    pub version: u32,
+    /// Identifier chosen by the page, which is echoed back in the [`Response`]
+    pub id: u64,
+    /// The current value of each input, keyed by the input's name (`x0`, `x1`, ...)
+    #[serde(default)]
+    pub inputs: BTreeMap<String, Value>,
+    /// A CSV file to run the function on row by row, in place of `inputs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<String>,
+    /// Whether to cancel the function running for request `id`, instead of running it again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancel: bool,
+}
+
+/// The answer to a [`Request`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Response {
+    /// Version of the protocol that the response was written for
+    pub version: u32,
+    /// Identifier of the [`Request`] being answered
+    pub id: u64,
+    /// What came of running the function
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+impl Response {
+    /// Answer a request with the outputs of the function
+    pub fn outputs(id: u64, outputs: Vec<OutputValue>) -> Self {
+        Self {
+            version: VERSION,
+            id,
+            outcome: Outcome::Outputs(outputs),
+        }
+    }
+
+    /// Answer a request with the outputs of a function that is still running
+    pub fn partial(id: u64, outputs: Vec<OutputValue>) -> Self {
+        Self {
+            version: VERSION,
+            id,
+            outcome: Outcome::Partial(outputs),
+        }
+    }
+
+    /// Report an error in the outputs of a function that is still running
+    pub fn partial_error<E: Display>(id: u64, error: E) -> Self {
+        Self {
+            version: VERSION,
+            id,
+            outcome: Outcome::PartialError(error.to_string()),
+        }
+    }
+
+    /// Answer a batch request with the results for every row
+    pub fn batch(id: u64, batch: Batch) -> Self {
+        Self {
+            version: VERSION,
+            id,
+            outcome: Outcome::Batch(batch),
+        }
+    }
+
+    /// Answer a request with an error
+    pub fn error<E: Display>(id: u64, error: E) -> Self {
+        Self {
+            version: VERSION,
+            id,
+            outcome: Outcome::Error(error.to_string()),
+        }
+    }
+}
+
+/// The body of a [`Response`], serialized as an `outputs`, `partial`, `partial_error`, `batch` or
+/// `error` field
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// The function ran, and produced these outputs
+    Outputs(Vec<OutputValue>),
+    /// The function is still running, and has produced these outputs so far
+    Partial(Vec<OutputValue>),
+    /// The function is still running, but the outputs it produced could not be shown
+    #[serde(rename = "partial_error")]
+    PartialError(String),
+    /// The function ran on every row of a CSV file
+    Batch(Batch),
+    /// The function could not be run
+    Error(String),
+}
+
+/// A single output of the function
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OutputValue {
+    /// The raw value
+    pub value: Value,
+    /// HTML that shows the output, label and all, in the page
+    pub display: String,
+}
+
+/// The results of running the function on every row of a CSV file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Batch {
+    /// The CSV file, with a column added for each output (and one for errors, if any row failed)
+    pub csv: String,
+    /// HTML that shows the results as a table, along with a link to download them
+    pub display: String,
+}
@@ -0,0 +1,104 @@
+//! Serving the GUI over HTTP, for when it should be opened in a browser instead of a window
+
+use std::{
+    error::Error,
+    fmt::Display,
+    net::{TcpListener, ToSocketAddrs},
+};
+
+use num_traits::Float;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{html_chunks::Transport, Teaser};
+
+impl<F: 'static + Float + Display> Teaser<F> {
+    /// Serve the GUI over HTTP instead of opening a window. The page is available at `/`, and the
+    /// page submits its inputs with a `POST` to `/submit`, which answers with the same JSON
+    /// messages as the window (see [`protocol`](crate::protocol)). This blocks for as long as the
+    /// server is running, and returns an error if the server can't be started, if the inputs
+    /// don't match the arguments of the function or if two inputs (or two outputs) share a label.
+    /// ```rust, no_run
+    /// use tease::{Input, Teaser};
+    /// Teaser::default()
+    ///     .with_title("Addition".to_string())
+    ///     .with_inputs(vec![Input::default(); 2])
+    ///     .with_function(|x: Vec<f32>| x.iter().sum())
+    ///     .serve("127.0.0.1:8080")
+    ///     .unwrap();
+    /// ```
+    /// Once it is running, any HTTP client can use it, e.g.
+    /// `curl -d '{"version": 1, "id": 0, "inputs": {"x0": 1, "x1": 2}}' http://127.0.0.1:8080/submit`.
+    ///
+    /// Scripts will find `POST /api/predict` easier to work with. It takes the value of each input
+    /// keyed by its label, and answers with the value of each output keyed by its label (or with
+    /// an `error`), e.g. `curl -d '{"Input 0": 1, "Input 1": 2}' http://127.0.0.1:8080/api/predict`
+    /// gives `{"Result": 3.0}`. An OpenAPI document describing it is served at
+    /// `/api/openapi.json`.
+    pub fn serve<A: ToSocketAddrs>(self, addr: A) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.serve_listener(TcpListener::bind(addr)?)
+    }
+
+    /// Serve the GUI over HTTP, just like [`serve`](Teaser::serve), on a listener that is already
+    /// bound. Binding to port 0 and asking the listener for its address is a handy way to pick a
+    /// free port, in tests say.
+    /// ```rust, no_run
+    /// use std::net::TcpListener;
+    /// use tease::Teaser;
+    /// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    /// println!("Serving at http://{}", listener.local_addr().unwrap());
+    /// Teaser::<f32>::default().serve_listener(listener).unwrap();
+    /// ```
+    pub fn serve_listener(self, listener: TcpListener) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.validate()?;
+        self.check_labels()?;
+        let html = self.get_html(Transport::Http);
+        let openapi = self.openapi().to_string();
+        let server = Server::from_listener(listener, None)?;
+        println!("Serving {} at http://{}", self.title, server.server_addr());
+
+        for mut request in server.incoming_requests() {
+            let response = match (request.method(), request.url()) {
+                (Method::Get, "/") => Response::from_string(html.as_str())
+                    .with_header(content_type("text/html; charset=utf-8")),
+                (Method::Post, "/submit") => {
+                    let mut message = String::new();
+                    match request.as_reader().read_to_string(&mut message) {
+                        Ok(_) => Response::from_string(self.respond(&message))
+                            .with_header(content_type("application/json")),
+                        Err(error) => {
+                            Response::from_string(error.to_string()).with_status_code(400)
+                        }
+                    }
+                }
+                (Method::Post, "/api/predict") => {
+                    let mut message = String::new();
+                    match request.as_reader().read_to_string(&mut message) {
+                        Ok(_) => {
+                            let (status, body) = self.predict(&message);
+                            Response::from_string(body)
+                                .with_status_code(status)
+                                .with_header(content_type("application/json"))
+                        }
+                        Err(error) => {
+                            Response::from_string(error.to_string()).with_status_code(400)
+                        }
+                    }
+                }
+                (Method::Get, "/api/openapi.json") => Response::from_string(openapi.as_str())
+                    .with_header(content_type("application/json")),
+                _ => Response::from_string("Not found").with_status_code(404),
+            };
+
+            if let Err(error) = request.respond(response) {
+                eprintln!("Failed to respond to request: {error}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Build a `Content-Type` header
+fn content_type(value: &str) -> Header {
+    Header::from_bytes(&b"Content-Type"[..], value.as_bytes()).unwrap()
+}
@@ -0,0 +1,75 @@
+//! Sending the outputs of a function to the page while it is still running
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::{pair, protocol::Response, render, IntoValues, Output};
+
+/// Where the responses to a request are sent, from whichever thread produces them
+pub(crate) type Reply = Arc<dyn Send + Sync + Fn(Response)>;
+
+/// Hands the outputs of a function added using
+/// [`with_streaming_function`](crate::Teaser::with_streaming_function) to the page before the
+/// function is done
+pub struct Emitter {
+    /// Identifier of the request being answered
+    id: u64,
+    /// The outputs that the emitted values are shown in
+    outputs: Vec<Output>,
+    /// Where the emitted outputs are sent, or `None` if nobody is listening for them
+    reply: Option<Reply>,
+    /// Raised once nobody is waiting for the function any more
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Emitter {
+    /// An emitter that sends its outputs to `reply` (if any), as partial answers to request `id`,
+    /// and that reports the run as cancelled once `cancelled` is raised
+    #[cfg(feature = "window")]
+    pub(crate) fn new(
+        id: u64,
+        outputs: Vec<Output>,
+        reply: Option<Reply>,
+        cancelled: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            id,
+            outputs,
+            reply,
+            cancelled,
+        }
+    }
+
+    /// An emitter whose outputs are thrown away, for when only the final outputs can be shown
+    pub(crate) fn discard() -> Self {
+        Self {
+            id: 0,
+            outputs: vec![],
+            reply: None,
+            cancelled: Arc::default(),
+        }
+    }
+
+    /// Whether the run has been cancelled, by the user or by a newer request, in which case
+    /// whatever the function returns is thrown away and it may as well stop early
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Show `outputs` in the GUI in place of whatever was there, with one value for each output
+    /// just like the value returned by the function. An error, if the outputs are a `Result` or
+    /// can't be shown, is shown instead, and the function carries on running.
+    pub fn emit<R: IntoValues>(&self, outputs: R) {
+        if let Some(reply) = &self.reply {
+            let outputs = outputs
+                .into_values()
+                .and_then(|y| render(&pair(&self.outputs, y)?));
+            reply(match outputs {
+                Ok(outputs) => Response::partial(self.id, outputs),
+                Err(error) => Response::partial_error(self.id, error),
+            });
+        }
+    }
+}
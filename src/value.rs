@@ -0,0 +1,788 @@
+//! The values passed between the GUI and the function, and the conversions between them and
+//! ordinary rust types
+
+use std::{
+    any::type_name,
+    fmt::{self, Display},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use num_traits::Float;
+
+use crate::pixels::Image;
+
+/// A value passed to or returned from a function, for functions that work with more than numbers
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    /// A number
+    Float(f64),
+    /// A whole number
+    Int(i64),
+    /// A yes or no
+    Bool(bool),
+    /// A piece of text
+    Text(String),
+    /// A sequence of values
+    List(Vec<Value>),
+    /// Raw bytes, such as the contents of an image file
+    Bytes(Vec<u8>),
+    /// A file uploaded by the user
+    File(Upload),
+    /// A recording
+    Audio(Audio),
+    /// An image
+    Image(Image),
+}
+
+/// A file uploaded by the user, along with what the page knew about it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Upload {
+    /// Name of the file, without the directory it came from
+    pub name: String,
+    /// MIME type of the file, as guessed by the page (e.g. `text/csv`), or empty if unknown
+    pub mime: String,
+    /// Contents of the file
+    pub bytes: Vec<u8>,
+}
+
+/// A mono recording, as a sequence of samples between -1 and 1
+#[derive(Clone, Debug, PartialEq)]
+pub struct Audio {
+    /// The samples of the recording, in order
+    pub samples: Vec<f32>,
+    /// Number of samples per second
+    pub sample_rate: u32,
+}
+
+impl Audio {
+    /// Length of the recording, in seconds
+    pub fn duration(&self) -> f64 {
+        self.samples.len() as f64 / f64::from(self.sample_rate.max(1))
+    }
+
+    /// Encode the recording as a 16-bit PCM WAV file, or explain why it can't be
+    pub fn to_wav(&self) -> Result<Vec<u8>, String> {
+        // Sizes in the header are 32-bit, so the recording has to fit in 4 GiB at 2 bytes a sample
+        let byte_rate = u32::try_from(2 * u64::from(self.sample_rate)).map_err(|_| {
+            format!(
+                "recorded at {} samples per second, which is too fast for a WAV file",
+                self.sample_rate
+            )
+        })?;
+        let data_size = wav_data_size(self.samples.len())?;
+        let mut wav = Vec::with_capacity(44 + data_size as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes()); // size of the format chunk
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // one channel
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&byte_rate.to_le_bytes()); // bytes per second
+        wav.extend_from_slice(&2u16.to_le_bytes()); // bytes per sample
+        wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_size.to_le_bytes());
+        for sample in &self.samples {
+            let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        Ok(wav)
+    }
+}
+
+/// The size of the data chunk of a WAV file holding `samples` 16-bit samples, as long as the whole
+/// file fits in the 32-bit sizes of its header
+fn wav_data_size(samples: usize) -> Result<u32, String> {
+    u32::try_from(2 * samples as u64)
+        .ok()
+        .filter(|size| size.checked_add(36).is_some())
+        .ok_or_else(|| format!("{samples} samples long, which is too long for a WAV file"))
+}
+
+impl Value {
+    /// The number held by the value, if it is one. Whole numbers and yes or no values (as 1 or 0)
+    /// count as numbers too.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(x) => Some(*x),
+            Value::Int(x) => Some(*x as f64),
+            Value::Bool(x) => Some(if *x { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+
+    /// The whole number held by the value, if it is one
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(x) => Some(*x),
+            Value::Float(x) if x.fract() == 0.0 => Some(*x as i64),
+            Value::Bool(x) => Some(*x as i64),
+            _ => None,
+        }
+    }
+
+    /// The yes or no held by the value, if it is one. Numbers count as yes unless they are zero.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(x) => Some(*x),
+            Value::Float(x) => Some(*x != 0.0),
+            Value::Int(x) => Some(*x != 0),
+            _ => None,
+        }
+    }
+
+    /// The text held by the value, if it is some
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The values held by the value, if it is a list
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The bytes held by the value, if it holds some
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// The file held by the value, if it holds one
+    pub fn as_file(&self) -> Option<&Upload> {
+        match self {
+            Value::File(upload) => Some(upload),
+            _ => None,
+        }
+    }
+
+    /// The recording held by the value, if it holds one
+    pub fn as_audio(&self) -> Option<&Audio> {
+        match self {
+            Value::Audio(audio) => Some(audio),
+            _ => None,
+        }
+    }
+
+    /// The image held by the value, if it holds one
+    pub fn as_image(&self) -> Option<&Image> {
+        match self {
+            Value::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+
+    /// What kind of value this is
+    pub fn kind(&self) -> Kind {
+        match self {
+            Value::Float(_) => Kind::Float,
+            Value::Int(_) => Kind::Int,
+            Value::Bool(_) => Kind::Bool,
+            Value::Text(_) => Kind::Text,
+            Value::List(_) => Kind::List,
+            Value::Bytes(_) => Kind::Bytes,
+            Value::File(_) => Kind::File,
+            Value::Audio(_) => Kind::Audio,
+            Value::Image(_) => Kind::Image,
+        }
+    }
+
+    /// Wrap up a number of any float type
+    pub(crate) fn from_float<F: Float>(x: F) -> Self {
+        Value::Float(x.to_f64().unwrap_or(f64::NAN))
+    }
+
+    /// The number held by the value, as any float type
+    pub(crate) fn to_float<F: Float>(&self) -> Option<F> {
+        self.as_float().and_then(F::from)
+    }
+
+    /// The value as it is sent to the page
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Float(x) => (*x).into(),
+            Value::Int(x) => (*x).into(),
+            Value::Bool(x) => (*x).into(),
+            Value::Text(text) => text.as_str().into(),
+            Value::List(values) => values.iter().map(Value::to_json).collect(),
+            Value::Bytes(bytes) => STANDARD.encode(bytes).into(),
+            Value::File(upload) => serde_json::json!({
+                "name": upload.name,
+                "mime": upload.mime,
+                "bytes": STANDARD.encode(&upload.bytes),
+            }),
+            Value::Audio(audio) => serde_json::json!({
+                "sample_rate": audio.sample_rate,
+                "samples": STANDARD.encode(
+                    audio.samples.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>()
+                ),
+            }),
+            Value::Image(image) => serde_json::json!({
+                "width": image.width,
+                "height": image.height,
+                "color_mode": image.color_mode.name(),
+                "pixels": STANDARD.encode(&image.pixels),
+            }),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Float(x) => write!(f, "{x}"),
+            Value::Int(x) => write!(f, "{x}"),
+            Value::Bool(x) => write!(f, "{x}"),
+            Value::Text(text) => write!(f, "{text}"),
+            Value::List(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Value::Bytes(bytes) => write!(f, "{} bytes", bytes.len()),
+            Value::File(upload) => write!(f, "{} ({} bytes)", upload.name, upload.bytes.len()),
+            Value::Audio(audio) => write!(f, "{:.2} seconds of audio", audio.duration()),
+            Value::Image(image) => write!(f, "{}x{} image", image.width, image.height),
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Float(x)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(x: f32) -> Self {
+        Value::Float(x.into())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(x: i64) -> Self {
+        Value::Int(x)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(x: bool) -> Self {
+        Value::Bool(x)
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<Audio> for Value {
+    fn from(audio: Audio) -> Self {
+        Value::Audio(audio)
+    }
+}
+
+impl From<Image> for Value {
+    fn from(image: Image) -> Self {
+        Value::Image(image)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+/// The kinds of [`Value`] that an input can produce
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Kind {
+    /// A number
+    Float,
+    /// A whole number
+    Int,
+    /// A yes or no
+    Bool,
+    /// A piece of text
+    Text,
+    /// A sequence of values
+    List,
+    /// Raw bytes
+    Bytes,
+    /// A file uploaded by the user
+    File,
+    /// A recording
+    Audio,
+    /// An image
+    Image,
+}
+
+impl Kind {
+    /// Whether values of this kind can be read as numbers
+    fn is_numeric(&self) -> bool {
+        matches!(self, Kind::Float | Kind::Int | Kind::Bool)
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Kind::Float => "a number",
+            Kind::Int => "a whole number",
+            Kind::Bool => "a yes or no",
+            Kind::Text => "text",
+            Kind::List => "a list",
+            Kind::Bytes => "bytes",
+            Kind::File => "a file",
+            Kind::Audio => "audio",
+            Kind::Image => "an image",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// Types that a function can take as an argument, read out of the [`Value`] of an input
+pub trait FromValue: Sized {
+    /// Whether an input producing values of `kind` can be read as this type
+    fn accepts(kind: Kind) -> bool;
+
+    /// Read the value as this type, if it can be
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+impl FromValue for Value {
+    fn accepts(_kind: Kind) -> bool {
+        true
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        Some(value)
+    }
+}
+
+/// Implement [`FromValue`] for number types
+macro_rules! impl_from_value_for_number {
+    ($($t:ty => $read:expr),+) => {
+        $(
+            impl FromValue for $t {
+                fn accepts(kind: Kind) -> bool {
+                    kind.is_numeric()
+                }
+
+                fn from_value(value: Value) -> Option<Self> {
+                    $read(&value)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_value_for_number!(
+    f64 => Value::as_float,
+    f32 => |value: &Value| value.to_float(),
+    i64 => Value::as_int,
+    i32 => |value: &Value| value.as_int().and_then(|x| x.try_into().ok()),
+    usize => |value: &Value| value.as_int().and_then(|x| x.try_into().ok()),
+    bool => Value::as_bool
+);
+
+impl FromValue for String {
+    fn accepts(kind: Kind) -> bool {
+        kind == Kind::Text
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for Upload {
+    fn accepts(kind: Kind) -> bool {
+        kind == Kind::File
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::File(upload) => Some(upload),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for Audio {
+    fn accepts(kind: Kind) -> bool {
+        kind == Kind::Audio
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Audio(audio) => Some(audio),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn accepts(kind: Kind) -> bool {
+        kind == Kind::List
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::List(values) => values.into_iter().map(T::from_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// The arguments of a function added using `with_typed_function`, read from the inputs in order,
+/// either as a tuple with one element for each input or as a `Vec` holding every input
+pub trait FromInputs: Sized {
+    /// Check that inputs producing values of `kinds` can be read as these arguments
+    fn check(kinds: &[Kind]) -> Result<(), String>;
+
+    /// Read the values of the inputs as these arguments
+    fn from_inputs(values: Vec<Value>) -> Result<Self, String>;
+}
+
+/// Implement [`FromInputs`] for a tuple of [`FromValue`] types
+macro_rules! impl_from_inputs_for_tuple {
+    ($n:expr; $($t:ident),+) => {
+        impl<$($t: FromValue),+> FromInputs for ($($t,)+) {
+            fn check(kinds: &[Kind]) -> Result<(), String> {
+                if kinds.len() != $n {
+                    return Err(format!(
+                        "The function takes {} arguments, but there are {} inputs",
+                        $n,
+                        kinds.len()
+                    ));
+                }
+                let mut kinds = kinds.iter().enumerate();
+                $(
+                    let (idx, kind) = kinds.next().unwrap();
+                    if !$t::accepts(*kind) {
+                        return Err(format!(
+                            "Input {idx} produces {kind}, which can't be read as a `{}`",
+                            short_type_name::<$t>()
+                        ));
+                    }
+                )+
+                Ok(())
+            }
+
+            fn from_inputs(values: Vec<Value>) -> Result<Self, String> {
+                if values.len() != $n {
+                    return Err(format!(
+                        "The function takes {} arguments, but there are {} inputs",
+                        $n,
+                        values.len()
+                    ));
+                }
+                let mut values = values.into_iter().enumerate();
+                Ok(($(
+                    {
+                        let (idx, value) = values.next().unwrap();
+                        let kind = value.kind();
+                        $t::from_value(value).ok_or_else(|| {
+                            format!(
+                                "Input {idx} is {kind}, which can't be read as a `{}`",
+                                short_type_name::<$t>()
+                            )
+                        })?
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_inputs_for_tuple!(1; A);
+impl_from_inputs_for_tuple!(2; A, B);
+impl_from_inputs_for_tuple!(3; A, B, C);
+impl_from_inputs_for_tuple!(4; A, B, C, D);
+impl_from_inputs_for_tuple!(5; A, B, C, D, E);
+impl_from_inputs_for_tuple!(6; A, B, C, D, E, G);
+impl_from_inputs_for_tuple!(7; A, B, C, D, E, G, H);
+impl_from_inputs_for_tuple!(8; A, B, C, D, E, G, H, I);
+
+/// Any number of arguments of the same type, one for each input, for functions with too many
+/// inputs to spell out as a tuple
+impl<T: FromValue> FromInputs for Vec<T> {
+    fn check(kinds: &[Kind]) -> Result<(), String> {
+        match kinds.iter().position(|kind| !T::accepts(*kind)) {
+            Some(idx) => Err(format!(
+                "Input {idx} produces {}, which can't be read as a `{}`",
+                kinds[idx],
+                short_type_name::<T>()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn from_inputs(values: Vec<Value>) -> Result<Self, String> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let kind = value.kind();
+                T::from_value(value).ok_or_else(|| {
+                    format!(
+                        "Input {idx} is {kind}, which can't be read as a `{}`",
+                        short_type_name::<T>()
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// The name of a type without the paths of the modules it comes from, e.g. `Vec<String>`
+fn short_type_name<T>() -> String {
+    let mut name = String::new();
+    let mut word = String::new();
+    let mut chars = type_name::<T>().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            word.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+        } else {
+            name.push_str(&word);
+            name.push(c);
+            word.clear();
+        }
+    }
+    name + &word
+}
+
+/// Types that a function can return, to be shown in an output
+pub trait IntoValue {
+    /// Convert into a [`Value`]
+    fn into_value(self) -> Value;
+}
+
+/// Implement [`IntoValue`] and [`IntoValues`] for types that can already become a [`Value`]
+macro_rules! impl_into_value {
+    ($($t:ty),+) => {
+        $(
+            impl IntoValue for $t {
+                fn into_value(self) -> Value {
+                    self.into()
+                }
+            }
+
+            impl IntoValues for $t {
+                fn into_values(self) -> Result<Vec<Value>, String> {
+                    Ok(vec![self.into_value()])
+                }
+            }
+        )+
+    };
+}
+
+impl_into_value!(Value, f64, f32, i64, bool, String, &str, Audio, Image);
+
+impl IntoValue for i32 {
+    fn into_value(self) -> Value {
+        Value::Int(self.into())
+    }
+}
+
+impl IntoValue for usize {
+    fn into_value(self) -> Value {
+        Value::Int(self as i64)
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+/// Implement [`IntoValue`] for a tuple of [`IntoValue`] types, which becomes a list. (Returned on
+/// its own, a tuple is still one output per element; this is for tuples inside a `Vec`, like the
+/// points of a plot.)
+macro_rules! impl_into_value_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: IntoValue),+> IntoValue for ($($t,)+) {
+            #[allow(non_snake_case)]
+            fn into_value(self) -> Value {
+                let ($($t,)+) = self;
+                Value::List(vec![$($t.into_value()),+])
+            }
+        }
+    };
+}
+
+impl_into_value_for_tuple!(A, B);
+impl_into_value_for_tuple!(A, B, C);
+impl_into_value_for_tuple!(A, B, C, D);
+impl_into_value_for_tuple!(A, B, C, D, E);
+impl_into_value_for_tuple!(A, B, C, D, E, G);
+
+/// The values returned by a function added using `with_typed_function`. A tuple gives one value for
+/// each output, and anything else is a single output, so a `Vec` is one output holding a list.
+/// (A function added using `with_multi_function` returns [`IntoOutputs`](crate::IntoOutputs)
+/// instead, where a `Vec` has one value for each output.)
+pub trait IntoValues {
+    /// One value for each output, in order, or an error to show instead
+    fn into_values(self) -> Result<Vec<Value>, String>;
+}
+
+impl IntoValues for i32 {
+    fn into_values(self) -> Result<Vec<Value>, String> {
+        Ok(vec![self.into_value()])
+    }
+}
+
+impl IntoValues for usize {
+    fn into_values(self) -> Result<Vec<Value>, String> {
+        Ok(vec![self.into_value()])
+    }
+}
+
+impl<T: IntoValue> IntoValues for Vec<T> {
+    fn into_values(self) -> Result<Vec<Value>, String> {
+        Ok(vec![self.into_value()])
+    }
+}
+
+/// Implement [`IntoValues`] for a tuple of [`IntoValue`] types
+macro_rules! impl_into_values_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: IntoValue),+> IntoValues for ($($t,)+) {
+            #[allow(non_snake_case)]
+            fn into_values(self) -> Result<Vec<Value>, String> {
+                let ($($t,)+) = self;
+                Ok(vec![$($t.into_value()),+])
+            }
+        }
+    };
+}
+
+impl_into_values_for_tuple!(A);
+impl_into_values_for_tuple!(A, B);
+impl_into_values_for_tuple!(A, B, C);
+impl_into_values_for_tuple!(A, B, C, D);
+impl_into_values_for_tuple!(A, B, C, D, E);
+impl_into_values_for_tuple!(A, B, C, D, E, G);
+
+impl<R: IntoValues, E: Display> IntoValues for Result<R, E> {
+    fn into_values(self) -> Result<Vec<Value>, String> {
+        self.map_err(|error| error.to_string())?.into_values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wav_data_size, Audio, FromInputs, Kind};
+
+    /// Read the little-endian `u32` at `offset`
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    }
+
+    #[test]
+    fn wav_headers_hold_the_sizes_and_byte_rate() {
+        let audio = Audio {
+            samples: vec![0.0, 1.0, -1.0],
+            sample_rate: 8000,
+        };
+        let wav = audio.to_wav().unwrap();
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(u32_at(&wav, 4), 36 + 6);
+        assert_eq!(u32_at(&wav, 24), 8000);
+        assert_eq!(u32_at(&wav, 28), 16000);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(&wav, 40), 6);
+        assert_eq!(&wav[44..], [0, 0, 0xff, 0x7f, 0x01, 0x80]);
+    }
+
+    #[test]
+    fn wav_byte_rates_must_fit_in_the_header() {
+        let audio = Audio {
+            samples: vec![],
+            sample_rate: u32::MAX / 2 + 1,
+        };
+        assert_eq!(
+            audio.to_wav(),
+            Err(
+                "recorded at 2147483648 samples per second, which is too fast for a WAV file"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn wav_sizes_must_fit_in_the_header() {
+        assert_eq!(wav_data_size(1000), Ok(2000));
+        assert_eq!(
+            wav_data_size((u32::MAX as usize - 36) / 2),
+            Ok(u32::MAX - 37)
+        );
+        assert_eq!(
+            wav_data_size(u32::MAX as usize / 2 + 1),
+            Err("2147483648 samples long, which is too long for a WAV file".to_string())
+        );
+        assert!(wav_data_size((u32::MAX as usize - 35) / 2 + 1).is_err());
+    }
+
+    #[test]
+    fn arguments_must_match_the_kinds_of_the_inputs() {
+        assert_eq!(<(f64, f64)>::check(&[Kind::Float, Kind::Float]), Ok(()));
+        assert_eq!(
+            <(f64, String)>::check(&[Kind::Float, Kind::Float]),
+            Err("Input 1 produces a number, which can't be read as a `String`".to_string())
+        );
+        assert_eq!(
+            <(Vec<bool>,)>::check(&[Kind::Text]),
+            Err("Input 0 produces text, which can't be read as a `Vec<bool>`".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_must_match_the_number_of_inputs() {
+        assert_eq!(
+            <(f64, f64)>::check(&[Kind::Float, Kind::Float, Kind::Float]),
+            Err("The function takes 2 arguments, but there are 3 inputs".to_string())
+        );
+        assert!(<(f64,)>::check(&[]).is_err());
+    }
+
+    #[test]
+    fn vecs_take_any_number_of_inputs_of_one_kind() {
+        assert_eq!(Vec::<f32>::check(&[Kind::Float; 10]), Ok(()));
+        assert_eq!(Vec::<f32>::check(&[]), Ok(()));
+        assert_eq!(
+            Vec::<f32>::check(&[Kind::Float, Kind::Bool, Kind::Text]),
+            Err("Input 2 produces text, which can't be read as a `f32`".to_string())
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! The functions behind [`export_wasm`](crate::export_wasm), which pass requests and responses
+//! between the page and a WebAssembly module through a buffer in the module's memory
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// The buffer that the page writes each request into, and reads each response out of
+    static BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Make room for a request of `len` bytes, returning where the page should write it
+pub fn reserve(len: usize) -> *mut u8 {
+    BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        buffer.clear();
+        buffer.resize(len, 0);
+        buffer.as_mut_ptr()
+    })
+}
+
+/// Where the page should read the last response from
+pub fn buffer() -> *const u8 {
+    BUFFER.with(|buffer| buffer.borrow().as_ptr())
+}
+
+/// Answer the request of `len` bytes in the buffer with `respond`, replacing it with the response
+/// and returning the length of the response
+pub fn respond(len: usize, respond: impl FnOnce(&str) -> String) -> usize {
+    let request = BUFFER.with(|buffer| {
+        let buffer = buffer.borrow();
+        String::from_utf8_lossy(&buffer[..len.min(buffer.len())]).into_owned()
+    });
+    let response = respond(&request).into_bytes();
+    let len = response.len();
+    BUFFER.with(|buffer| *buffer.borrow_mut() = response);
+    len
+}
+
+/// Export the function of a teaser from a WebAssembly module, so that a page written by
+/// [`Teaser::export_html`](crate::Teaser::export_html) can run it without a server. The teaser is
+/// built the first time the page sends a request.
+/// ```rust, no_run
+/// use tease::{export_wasm, Input, Teaser};
+///
+/// export_wasm!(Teaser::default()
+///     .with_inputs(vec![Input::default(); 2])
+///     .with_function(|x: Vec<f32>| x.iter().sum()));
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! export_wasm {
+    ($teaser:expr) => {
+        ::std::thread_local! {
+            static TEASER: ::std::boxed::Box<dyn Fn(&str) -> ::std::string::String> = {
+                let teaser = $teaser;
+                ::std::boxed::Box::new(move |message: &str| teaser.respond(message))
+            };
+        }
+
+        #[no_mangle]
+        pub extern "C" fn tease_reserve(len: usize) -> *mut u8 {
+            $crate::wasm::reserve(len)
+        }
+
+        #[no_mangle]
+        pub extern "C" fn tease_buffer() -> *const u8 {
+            $crate::wasm::buffer()
+        }
+
+        #[no_mangle]
+        pub extern "C" fn tease_respond(len: usize) -> usize {
+            TEASER.with(|respond| $crate::wasm::respond(len, respond))
+        }
+    };
+}
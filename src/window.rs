@@ -0,0 +1,275 @@
+//! Showing the GUI in a window of its own, with wry
+
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use num_traits::Float;
+use wry::{
+    application::{
+        event::{Event, StartCause, WindowEvent},
+        event_loop::{ControlFlow, EventLoop},
+        window::{Window, WindowBuilder},
+    },
+    webview::{WebViewAttributes, WebViewBuilder},
+};
+
+use crate::{
+    call,
+    html_chunks::Transport,
+    protocol::{self, Request, Response},
+    render,
+    stream::Reply,
+    Emitter, Teaser,
+};
+
+/// The flag of the run that the page is waiting on, which is raised to cancel it
+type Running = RefCell<Arc<AtomicBool>>;
+
+impl<F: 'static + Float + Display> Teaser<F> {
+    /// Run the GUI. This panics if the inputs don't match the arguments of the function.
+    pub fn run(self) {
+        if let Err(error) = self.validate() {
+            panic!("{error}");
+        }
+
+        let html = self.get_html(Transport::Ipc);
+        let running = Running::default();
+
+        // Responses are handed to the event loop, which passes them on to the page
+        let event_loop = EventLoop::<Response>::with_user_event();
+        let proxy = event_loop.create_proxy();
+        let window = WindowBuilder::new()
+            .with_title(self.title.clone())
+            .build(&event_loop)
+            .unwrap();
+
+        let webview_settings = WebViewAttributes {
+            devtools: true,
+            ..Default::default()
+        };
+        let mut webview_builder = WebViewBuilder::new(window).unwrap();
+        webview_builder.webview = webview_settings;
+        let webview = webview_builder
+            .with_html(html)
+            .unwrap()
+            .with_ipc_handler(move |_window: &Window, req: String| {
+                let proxy = proxy.clone();
+                self.dispatch(&req, &running, move |response| {
+                    // The window may have been closed while the function was running
+                    let _ = proxy.send_event(response);
+                });
+            })
+            .build()
+            .unwrap();
+
+        event_loop.run(move |event, _, control_flow| {
+            *control_flow = ControlFlow::Wait;
+
+            match event {
+                Event::NewEvents(StartCause::Init) => println!("Wry application started!"),
+                Event::UserEvent(response) => {
+                    let response = serde_json::to_string(&response)
+                        .expect("Responses can always be serialized");
+                    webview
+                        .evaluate_script(&format!("handle_response({response})"))
+                        .expect("The page could not be handed the response");
+                }
+                Event::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                } => *control_flow = ControlFlow::ExitWithCode(0),
+                _ => {}
+            }
+        });
+    }
+
+    /// Answer a JSON [`Request`] from the window by handing a [`Response`] to `reply`. A function
+    /// added using `with_background_function` or `with_streaming_function` is run on a worker
+    /// thread (for a batch as well as a single request), which replies with any outputs emitted
+    /// along the way and again once the function is done. Every message cancels the run before
+    /// it, since the page only waits on its latest request.
+    fn dispatch(
+        &self,
+        message: &str,
+        running: &Running,
+        reply: impl 'static + Send + Sync + Fn(Response),
+    ) {
+        let request = match serde_json::from_str::<Request>(message) {
+            Ok(request) => request,
+            Err(error) => return reply(Response::error(0, format!("Malformed request: {error}"))),
+        };
+        running.borrow().store(true, Ordering::Relaxed);
+        if request.cancel {
+            return reply(self.handle(request));
+        }
+        let function = match &self.background_function {
+            Some(function)
+                if !self.use_advanced_function && request.version == protocol::VERSION =>
+            {
+                function.clone()
+            }
+            _ => return reply(self.handle(request)),
+        };
+        let outputs = self.outputs.clone();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        *running.borrow_mut() = cancelled.clone();
+
+        // A batch is read here, but run on the worker thread just like a single request
+        if let Some(csv) = &request.batch {
+            let job = match self.read_batch(csv) {
+                Ok(job) => job,
+                Err(error) => return reply(Response::error(request.id, error)),
+            };
+            thread::spawn(move || {
+                let emitter = Emitter::new(request.id, vec![], None, cancelled);
+                let batch = job.run(|x| {
+                    if emitter.is_cancelled() {
+                        return Err("The run was cancelled".to_string());
+                    }
+                    call(&|x| function(x, &emitter), &outputs, x)
+                });
+                reply(Response::batch(request.id, batch))
+            });
+            return;
+        }
+
+        let inputs = match self.read_inputs(&request.inputs) {
+            Ok(inputs) => inputs,
+            Err(error) => return reply(Response::error(request.id, error)),
+        };
+
+        let reply: Reply = Arc::new(reply);
+        thread::spawn(move || {
+            let emitter = Emitter::new(request.id, outputs.clone(), Some(reply.clone()), cancelled);
+            let result = call(&|x| function(x, &emitter), &outputs, inputs);
+            reply(match result.and_then(|y| render(&y)) {
+                Ok(outputs) => Response::outputs(request.id, outputs),
+                Err(error) => Response::error(request.id, error),
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{mpsc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::Running;
+    use crate::{protocol::Response, Emitter, Input, Teaser};
+
+    /// Dispatch `message` to `teaser`, handing back the replies as they come
+    fn send(teaser: &Teaser<f32>, running: &Running, message: &str) -> mpsc::Receiver<Response> {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        teaser.dispatch(message, running, move |response: Response| {
+            let _ = sender.lock().unwrap().send(response);
+        });
+        receiver
+    }
+
+    /// Collect the replies to a message, up to and including the last one
+    fn replies(receiver: mpsc::Receiver<Response>) -> Vec<serde_json::Value> {
+        let mut replies = vec![];
+        for response in receiver.iter() {
+            let response = serde_json::to_value(response).unwrap();
+            let done = response.get("partial").is_none() && response.get("partial_error").is_none();
+            replies.push(response);
+            if done {
+                break;
+            }
+        }
+        replies
+    }
+
+    #[test]
+    fn background_functions_take_many_inputs_as_a_vec() {
+        let teaser = Teaser::default()
+            .with_inputs(vec![Input::default(); 10])
+            .with_background_function(|x: Vec<f32>| x.iter().sum::<f32>());
+        let inputs: serde_json::Map<_, _> = (0..10)
+            .map(|idx| (format!("x{idx}"), serde_json::json!(idx)))
+            .collect();
+        let message = serde_json::json!({ "version": 1, "id": 4, "inputs": inputs });
+        let replies = replies(send(&teaser, &Running::default(), &message.to_string()));
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0]["id"], 4);
+        assert_eq!(replies[0]["outputs"][0]["value"], 45.0);
+    }
+
+    #[test]
+    fn emitting_bad_outputs_leaves_the_function_running() {
+        let teaser =
+            Teaser::default().with_streaming_function(|(x,): (f32,), emitter: &Emitter| {
+                emitter.emit((x, x));
+                emitter.emit(x + 1.0);
+                x + 2.0
+            });
+        let message = r#"{"version":1,"id":5,"inputs":{"x0":1}}"#;
+        let replies = replies(send(&teaser, &Running::default(), message));
+        assert_eq!(replies.len(), 3);
+        assert_eq!(
+            replies[0]["partial_error"],
+            "The function returned 2 values, but there are 1 outputs"
+        );
+        assert_eq!(replies[1]["partial"][0]["value"], 2.0);
+        assert_eq!(replies[2]["outputs"][0]["value"], 3.0);
+    }
+
+    #[test]
+    fn cancelling_tells_the_function_to_stop() {
+        // The function waits to be cancelled, and reports whether it was
+        let teaser =
+            Teaser::default().with_streaming_function(|(_,): (f32,), emitter: &Emitter| {
+                let start = Instant::now();
+                while !emitter.is_cancelled() && start.elapsed() < Duration::from_secs(10) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                f32::from(u8::from(emitter.is_cancelled()))
+            });
+        let running = Running::default();
+        let run = send(
+            &teaser,
+            &running,
+            r#"{"version":1,"id":6,"inputs":{"x0":0}}"#,
+        );
+        let cancel = send(&teaser, &running, r#"{"version":1,"id":6,"cancel":true}"#);
+        assert_eq!(replies(cancel)[0]["error"], "The run was cancelled");
+        assert_eq!(replies(run)[0]["outputs"][0]["value"], 1.0);
+    }
+
+    #[test]
+    fn newer_requests_cancel_older_runs() {
+        let teaser =
+            Teaser::default().with_streaming_function(|(_,): (f32,), emitter: &Emitter| {
+                let start = Instant::now();
+                while !emitter.is_cancelled() && start.elapsed() < Duration::from_millis(500) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                f32::from(u8::from(emitter.is_cancelled()))
+            });
+        let running = Running::default();
+        let first = send(
+            &teaser,
+            &running,
+            r#"{"version":1,"id":7,"inputs":{"x0":0}}"#,
+        );
+        let second = send(
+            &teaser,
+            &running,
+            r#"{"version":1,"id":8,"inputs":{"x0":0}}"#,
+        );
+        assert_eq!(replies(first)[0]["outputs"][0]["value"], 1.0);
+        assert_eq!(replies(second)[0]["outputs"][0]["value"], 0.0);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+use tease::{Input, Output, Teaser};
+
+/// Serve a teaser that adds a number to one picked on a slider from 0 to 10 on a free port,
+/// returning its address
+fn start() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let slider = Input::Slider {
+            label: None,
+            min: 0.0,
+            max: 10.0,
+            step: 0.5,
+            initial_value: 1.0,
+        };
+        Teaser::default()
+            .with_inputs(vec![Input::default(), slider])
+            .with_function(|x: Vec<f32>| x.iter().sum())
+            .serve_listener(listener)
+            .unwrap();
+    });
+    addr
+}
+
+/// Send a request to the server, returning the status code and body of the response
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+#[test]
+fn serves_the_page() {
+    let (status, body) = request(start(), "GET", "/", "");
+    assert_eq!(status, 200);
+    assert!(body.contains("function send_message"));
+    assert!(body.contains("name=\"x1\""));
+}
+
+#[test]
+fn runs_the_function() {
+    let (status, body) = request(
+        start(),
+        "POST",
+        "/submit",
+        r#"{"version": 1, "id": 3, "inputs": {"x0": 1.5, "x1": 2}}"#,
+    );
+    assert_eq!(status, 200);
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(response["id"], 3);
+    assert_eq!(response["outputs"][0]["value"], 3.5);
+}
+
+#[test]
+fn reports_malformed_requests() {
+    let (status, body) = request(start(), "POST", "/submit", "{not json");
+    assert_eq!(status, 200);
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert!(response["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Malformed request"));
+}
+
+#[test]
+fn reports_unknown_paths() {
+    let (status, _) = request(start(), "GET", "/nowhere", "");
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn predicts_from_labelled_inputs() {
+    let (status, body) = request(
+        start(),
+        "POST",
+        "/api/predict",
+        r#"{"Input 0": 1.5, "Input 1": 2}"#,
+    );
+    assert_eq!(status, 200);
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(response["Result"], 3.5);
+}
+
+#[test]
+fn rejects_values_off_the_slider() {
+    let (status, body) = request(
+        start(),
+        "POST",
+        "/api/predict",
+        r#"{"Input 0": 1.5, "Input 1": 11}"#,
+    );
+    assert_eq!(status, 400);
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(
+        response["error"],
+        "Input 1 must be a number from 0 to 10, not 11"
+    );
+}
+
+#[test]
+fn refuses_to_serve_duplicate_labels() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let error = Teaser::default()
+        .with_inputs(vec![Input::default(); 2])
+        .with_outputs(vec![Output::default(); 2])
+        .with_multi_function(|x: Vec<f32>| vec![x[0], x[1]])
+        .serve_listener(listener)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Two outputs are labelled Result, so the API can't tell them apart"
+    );
+}
@@ -0,0 +1,57 @@
+use tease::{export_wasm, Input, Teaser};
+
+export_wasm!(Teaser::default()
+    .with_inputs(vec![Input::default(); 2])
+    .with_function(|x: Vec<f32>| x.iter().sum()));
+
+/// Send a message to the exported module the way the page does, returning its response
+fn send(message: &str) -> serde_json::Value {
+    let bytes = message.as_bytes();
+    let pointer = tease_reserve(bytes.len());
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), pointer, bytes.len()) };
+    let len = tease_respond(bytes.len());
+    let response = unsafe { std::slice::from_raw_parts(tease_buffer(), len) };
+    serde_json::from_slice(response).unwrap()
+}
+
+#[test]
+fn runs_the_function() {
+    let response = send(r#"{"version":1,"id":3,"inputs":{"x0":1.5,"x1":2}}"#);
+    assert_eq!(response["id"], 3);
+    assert_eq!(response["outputs"][0]["value"], 3.5);
+}
+
+#[test]
+fn answers_one_request_after_another() {
+    for x in 0..3 {
+        let message = format!(r#"{{"version":1,"id":{x},"inputs":{{"x0":{x},"x1":1}}}}"#);
+        let response = send(&message);
+        assert_eq!(response["id"], x);
+        assert_eq!(response["outputs"][0]["value"], f64::from(x + 1));
+    }
+}
+
+#[test]
+fn reports_malformed_requests() {
+    let response = send("not json");
+    assert!(response["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Malformed request"));
+}
+
+#[test]
+fn holds_large_requests_and_answers_them() {
+    let message = "x".repeat(1 << 20);
+    let pointer = tease::wasm::reserve(message.len());
+    unsafe { std::ptr::copy_nonoverlapping(message.as_ptr(), pointer, message.len()) };
+    let request = unsafe { std::slice::from_raw_parts(tease::wasm::buffer(), message.len()) };
+    assert_eq!(request, message.as_bytes());
+
+    let len = tease::wasm::respond(message.len(), |request| {
+        assert_eq!(request, message);
+        format!("{} bytes", request.len())
+    });
+    let response = unsafe { std::slice::from_raw_parts(tease::wasm::buffer(), len) };
+    assert_eq!(response, b"1048576 bytes");
+}